@@ -0,0 +1,177 @@
+//! Contains the attachments: files(images, PDFs, etc) copied into
+//! `jrnl_folder/attachments/YYYY/MM/` by `jrnl attach`, and linked from the entry:
+//! ```text
+//! - ![cat.jpg](../attachments/2026/10/3f2a9c1b7d4e8a06.jpg)
+//! - [receipt.pdf](../attachments/2026/10/b71e0d93c25f4a18.pdf)
+//! ```
+//! The files are named by the hash of their contents, so attaching the same file twice
+//! does not copy it twice.
+use crate::get_default_path;
+use crate::layout::{config_layout, journal_path};
+use crate::links::{Link, collect_links};
+use crate::utils::*;
+use chrono::{Datelike, NaiveDate};
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use std::{collections::HashSet, fs, io::ErrorKind, path::Path, process};
+
+/// Extensions of the attachments which are linked as images(`![...](...)`).
+const IMAGE_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "gif", "webp", "svg", "bmp"];
+
+/// Returns the path of the attachment, whose name is relative to `jrnl_folder`.
+pub fn attachment_path(attachment: &str) -> String {
+    format!("{}/jrnl_folder/{}", get_default_path(), attachment)
+}
+
+/// Returns the attachments linked in a line, relative to `jrnl_folder`
+/// (like `attachments/2026/10/3f2a9c1b7d4e8a06.jpg`).
+pub fn get_attachments_from_line(line: &str) -> Vec<String> {
+    split_markdown_links(line)
+        .1
+        .into_iter()
+        .filter_map(|url| {
+            let start = url.find("attachments/")?;
+            // `../attachments` from the entries, `attachments` from the notes
+            if start > 0 && !url[..start].ends_with('/') {
+                return None;
+            }
+            Some(url[start..].to_string())
+        })
+        .collect()
+}
+
+/// Copies the file into `attachments/YYYY/MM/`(of the date), and adds a link to it
+/// at the end of the date's entry.
+pub fn attach_file(file: &str, date: NaiveDate) {
+    let file = shellexpand::tilde(file).into_owned();
+    let contents = match fs::read(&file) {
+        Ok(data) => data,
+        Err(e) => match e.kind() {
+            ErrorKind::NotFound => {
+                eprintln!("{}", format!("The file {} does not exist.", file).red());
+                process::exit(1);
+            }
+            other => panic!("Error reading file: {other}"),
+        },
+    };
+    let jrnl_folder = format!("{}/jrnl_folder", get_default_path());
+    if !Path::new(&jrnl_folder).is_dir() {
+        eprintln!(
+            "{}",
+            "There doesn't seem to be a `jrnl_folder`. Please create it.".red()
+        );
+        process::exit(1);
+    }
+
+    let path = Path::new(&file);
+    let label = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or(file.clone());
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let hash = format!("{:x}", Sha256::digest(&contents));
+    let mut name = hash[..16].to_string();
+    if !extension.is_empty() {
+        name = format!("{}.{}", name, extension);
+    }
+    let folder = format!("attachments/{}/{:02}", date.year(), date.month());
+    let attachment = format!("{}/{}", folder, name);
+
+    if Path::new(&attachment_path(&attachment)).exists() {
+        println!("{} {}", "Already attached:".yellow(), attachment);
+    } else {
+        if let Err(e) = fs::create_dir_all(attachment_path(&folder)) {
+            panic!("An error occured: {}", e);
+        }
+        if let Err(e) = fs::write(attachment_path(&attachment), &contents) {
+            panic!("An error occured: {}", e);
+        }
+    }
+
+    // Relative to the journal file, like `../` for `2026/2026_10.md`
+    let depth = journal_path(&config_layout(), date).matches('/').count();
+    let image = if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        "!"
+    } else {
+        ""
+    };
+    let record = format!(
+        "- {}[{}]({}{})",
+        image,
+        label,
+        "../".repeat(depth),
+        attachment
+    );
+    add_record_to_entry(date, &record);
+    println!(
+        "{} [{}] {}",
+        "Attached:".green().bold(),
+        date.format("%Y-%m-%d").to_string().cyan(),
+        record
+    );
+}
+
+/// Returns every file in `jrnl_folder/attachments`, relative to `jrnl_folder`.
+pub fn get_attachment_files() -> Vec<String> {
+    get_attachment_files_in(&format!("{}/jrnl_folder", get_default_path()))
+}
+
+/// Returns every file in the `attachments` of the folder(a `jrnl_folder`), relative to it.
+pub fn get_attachment_files_in(root: &str) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let mut dirs: Vec<String> = vec![String::from("attachments")];
+    while let Some(dir) = dirs.pop() {
+        let paths = match fs::read_dir(format!("{}/{}", root, dir)) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+        for path in paths.flatten() {
+            let name = format!("{}/{}", dir, path.file_name().to_string_lossy());
+            if path.path().is_dir() {
+                dirs.push(name);
+            } else {
+                files.push(name);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Copies the attachments(relative to `jrnl_folder`) from one `jrnl_folder` to another.
+pub fn copy_attachments(from: &str, to: &str, attachments: &[String]) {
+    for attachment in attachments {
+        let target = format!("{}/{}", to, attachment);
+        if let Some(parent) = Path::new(&target).parent()
+            && let Err(e) = fs::create_dir_all(parent)
+        {
+            panic!("An error occured: {}", e);
+        }
+        if let Err(e) = fs::copy(format!("{}/{}", from, attachment), &target) {
+            panic!("An error occured: {}", e);
+        }
+    }
+}
+
+/// Returns the links to attachments which do not exist.
+pub fn get_missing_attachments() -> Vec<Link> {
+    collect_links(get_attachments_from_line)
+        .into_iter()
+        .filter(|link| !Path::new(&attachment_path(&link.target)).exists())
+        .collect()
+}
+
+/// Returns the attachments which are not linked from any entry or note.
+pub fn get_orphaned_attachments() -> Vec<String> {
+    let linked: HashSet<String> = collect_links(get_attachments_from_line)
+        .into_iter()
+        .map(|link| link.target)
+        .collect();
+    get_attachment_files()
+        .into_iter()
+        .filter(|attachment| !linked.contains(attachment))
+        .collect()
+}
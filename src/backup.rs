@@ -0,0 +1,518 @@
+//! Contains the backups: `jrnl backup` and `jrnl restore`.
+//!
+//! A backup is a compressed archive(`jrnl_backup_2026-10-18_213005.tar.gz`) of the whole
+//! `jrnl_folder`, and the config file as `config.toml`. They are kept in `backup_folder`,
+//! and only the latest backup of the last few days, weeks and months is kept:
+//! ```text
+//! backup_keep_daily = 7
+//! backup_keep_weekly = 4
+//! backup_keep_monthly = 12
+//! ```
+//! With `backup_before_rewrite`, the journal is also backed up before the commands which
+//! rewrite many files(`jrnl tags rename/merge`, `jrnl import`, `jrnl migrate`, `jrnl restore`).
+use crate::attachments::get_attachments_from_line;
+use crate::dates::{DateSpan, journal_now, parse_range, parse_span};
+use crate::funcs::*;
+use crate::get_default_path;
+use crate::layout::*;
+use crate::lock::*;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use colored::Colorize;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::{self, File},
+    io::Read,
+    path::{Component, Path},
+    process,
+};
+
+/// The format of the time in the names of the archives.
+const TIME_FORMAT: &str = "%Y-%m-%d_%H%M%S";
+
+/// Returns the folder with the backups: the given one, or the configured `backup_folder`.
+fn backup_folder(to: Option<&str>) -> String {
+    let folder = match to {
+        Some(to) => to.to_string(),
+        None => read_config().0.backup_folder,
+    };
+    if folder.is_empty() {
+        format!("{}/jrnl_backups", get_default_path())
+    } else {
+        shellexpand::tilde(&folder).into_owned()
+    }
+}
+
+/// Returns when the backup was made, from the name of the archive.
+fn backup_time(name: &str) -> Option<NaiveDateTime> {
+    let time = name.strip_prefix("jrnl_backup_")?.strip_suffix(".tar.gz")?;
+    NaiveDateTime::parse_from_str(time, TIME_FORMAT).ok()
+}
+
+/// Returns every file in `jrnl_folder`(relative to it) to be backed up. The hidden files
+/// used while writing(`.lock`, `.editing-<pid>`, `*.tmp`) are left out.
+fn get_files_to_back_up() -> Vec<String> {
+    let root = format!("{}/jrnl_folder", get_default_path());
+    let mut files: Vec<String> = Vec::new();
+    let mut dirs: Vec<String> = vec![String::new()];
+    while let Some(dir) = dirs.pop() {
+        let paths = match fs::read_dir(format!("{}/{}", root, dir)) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+        for path in paths.flatten() {
+            let name = path.file_name().to_string_lossy().to_string();
+            let rel_name = if dir.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", dir, name)
+            };
+            if path.path().is_dir() {
+                dirs.push(rel_name);
+            } else if !(name == ".lock" || name.starts_with(".editing-") || name.ends_with(".tmp"))
+            {
+                files.push(rel_name);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Makes a backup in the folder(or the configured `backup_folder`), and returns its path
+/// and the number of files in it.
+pub fn create_backup(to: Option<&str>) -> (String, usize) {
+    let root = format!("{}/jrnl_folder", get_default_path());
+    if !Path::new(&root).is_dir() {
+        eprintln!(
+            "{}",
+            "There doesn't seem to be a `jrnl_folder`. Please create it.".red()
+        );
+        process::exit(1);
+    }
+    let folder = backup_folder(to);
+    if let Err(e) = fs::create_dir_all(&folder) {
+        panic!("An error occured: {}", e);
+    }
+    let archive = format!(
+        "{}/jrnl_backup_{}.tar.gz",
+        folder,
+        journal_now().format(TIME_FORMAT)
+    );
+    let tmp_archive = format!("{}.tmp", archive);
+
+    // Nothing is written meanwhile, so that the backup is of a single moment
+    let _lock = lock_journal();
+    let files = get_files_to_back_up();
+    let result = (|| -> std::io::Result<()> {
+        let encoder = GzEncoder::new(File::create(&tmp_archive)?, Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for file in files.iter() {
+            builder.append_path_with_name(
+                format!("{}/{}", root, file),
+                format!("jrnl_folder/{}", file),
+            )?;
+        }
+        let config_file = shellexpand::tilde("~/.config/jrnl/config.toml").into_owned();
+        if Path::new(&config_file).exists() {
+            builder.append_path_with_name(config_file, "config.toml")?;
+        }
+        builder.into_inner()?.finish()?.sync_all()?;
+        fs::rename(&tmp_archive, &archive)
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp_archive);
+        panic!("An error occured: {}", e);
+    }
+    (archive, files.len())
+}
+
+/// Returns the backups(by when they were made) to be removed, so that only the latest one
+/// of the last `daily` days, `weekly` weeks and `monthly` months is kept.
+/// The latest backup is always kept, and nothing is removed if all are 0.
+fn backups_to_remove(
+    times: &[NaiveDateTime],
+    daily: u32,
+    weekly: u32,
+    monthly: u32,
+) -> Vec<NaiveDateTime> {
+    if daily == 0 && weekly == 0 && monthly == 0 {
+        return Vec::new();
+    }
+    let mut times: Vec<NaiveDateTime> = times.to_vec();
+    times.sort_by_key(|time| std::cmp::Reverse(*time));
+
+    let mut keep: HashSet<NaiveDateTime> = HashSet::new();
+    keep.extend(times.first());
+    for (count, kind) in [(daily, "day"), (weekly, "week"), (monthly, "month")] {
+        // Latest first, so the first backup seen in each period is its latest
+        let mut seen: Vec<(i32, u32, u32)> = Vec::new();
+        for time in times.iter() {
+            let date = time.date();
+            let period = match kind {
+                "day" => (date.year(), date.month(), date.day()),
+                "week" => (date.iso_week().year(), date.iso_week().week(), 0),
+                _ => (date.year(), date.month(), 0),
+            };
+            if seen.contains(&period) {
+                continue;
+            }
+            if seen.len() as u32 >= count {
+                break;
+            }
+            seen.push(period);
+            keep.insert(*time);
+        }
+    }
+    times
+        .into_iter()
+        .filter(|time| !keep.contains(time))
+        .collect()
+}
+
+/// Removes the old backups in the folder, as per the configured `backup_keep_*`.
+/// Returns how many were removed.
+fn rotate_backups(folder: &str) -> usize {
+    let config = read_config().0;
+    let Ok(paths) = fs::read_dir(folder) else {
+        return 0;
+    };
+    let mut backups: Vec<(NaiveDateTime, String)> = Vec::new();
+    for path in paths.flatten() {
+        let name = path.file_name().to_string_lossy().to_string();
+        if let Some(time) = backup_time(&name) {
+            backups.push((time, path.path().display().to_string()));
+        }
+    }
+    let times: Vec<NaiveDateTime> = backups.iter().map(|(time, _)| *time).collect();
+    let remove = backups_to_remove(
+        &times,
+        config.backup_keep_daily,
+        config.backup_keep_weekly,
+        config.backup_keep_monthly,
+    );
+    let mut removed = 0;
+    for (time, path) in backups {
+        if remove.contains(&time) && fs::remove_file(path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Backs up the journal, and removes the old backups.
+///
+/// # Sample output:
+/// ```text
+/// Backed up 42 files to /home/user/jrnl_backups/jrnl_backup_2026-10-18_213005.tar.gz
+/// Removed 2 old backup(s).
+/// ```
+pub fn run_backup(to: Option<&str>) {
+    let (archive, no_of_files) = create_backup(to);
+    println!(
+        "{} {} files to {}",
+        "Backed up".green().bold(),
+        no_of_files,
+        archive.underline()
+    );
+    let removed = rotate_backups(&backup_folder(to));
+    if removed > 0 {
+        println!("Removed {} old backup(s).", removed);
+    }
+}
+
+/// Backs up the journal if `backup_before_rewrite` is set. To be called before rewriting
+/// many files, after the user has confirmed it.
+///
+/// The old backups are not removed here but on the next `jrnl backup`, so that
+/// `jrnl restore` never removes the backup being restored from.
+pub fn backup_before_rewrite() {
+    if !read_config().0.backup_before_rewrite {
+        return;
+    }
+    let (archive, _) = create_backup(None);
+    println!(
+        "{} {}",
+        "Backed up the journal to".green(),
+        archive.underline()
+    );
+}
+
+/// Returns the files of the `jrnl_folder` in the archive, as (path relative to `jrnl_folder`,
+/// contents). The archive can be a path, or the name of one in `backup_folder`.
+fn read_archive(archive: &str) -> Vec<(String, Vec<u8>)> {
+    let mut path = shellexpand::tilde(archive).into_owned();
+    if !Path::new(&path).exists() {
+        path = format!("{}/{}", backup_folder(None), archive);
+    }
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(_) => {
+            eprintln!(
+                "{}",
+                format!("The backup {} does not exist.", archive).red()
+            );
+            process::exit(1);
+        }
+    };
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut tar_archive = tar::Archive::new(GzDecoder::new(file));
+    let result = (|| -> std::io::Result<()> {
+        for entry in tar_archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.into_owned();
+            // Nothing may be written outside of `jrnl_folder`
+            if !entry_path
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+            {
+                continue;
+            }
+            let Ok(rel_path) = entry_path.strip_prefix("jrnl_folder") else {
+                continue;
+            };
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let mut contents: Vec<u8> = Vec::new();
+            entry.read_to_end(&mut contents)?;
+            files.push((rel_path.display().to_string(), contents));
+        }
+        Ok(())
+    })();
+    if let Err(e) = result {
+        eprintln!(
+            "{}",
+            format!("{} is not a backup made by jrnl: {}", archive, e).red()
+        );
+        process::exit(1);
+    }
+    files
+}
+
+/// Writes a restored file into `jrnl_folder`, making its folders if needed.
+fn write_restored(rel_path: &str, contents: &[u8]) {
+    let path = format!("{}/jrnl_folder/{}", get_default_path(), rel_path);
+    if let Some(parent) = Path::new(&path).parent()
+        && let Err(e) = fs::create_dir_all(parent)
+    {
+        panic!("An error occured: {}", e);
+    }
+    let result = match std::str::from_utf8(contents) {
+        Ok(text) if is_journal_file(rel_path) => write_journal(&path, text),
+        _ => write_atomic(&path, contents),
+    };
+    if let Err(e) = result {
+        panic!("An error occured: {}", e);
+    }
+}
+
+/// Restores every file in the backup. Files not in the backup are left as they are, and
+/// the config file is not restored.
+fn restore_everything(archive: &str) {
+    let mut changed: Vec<(String, Vec<u8>, bool)> = Vec::new();
+    for (rel_path, contents) in read_archive(archive) {
+        let path = format!("{}/jrnl_folder/{}", get_default_path(), rel_path);
+        match fs::read(&path) {
+            Ok(current) if current == contents => (),
+            Ok(_) => changed.push((rel_path, contents, true)),
+            Err(_) => changed.push((rel_path, contents, false)),
+        }
+    }
+    if changed.is_empty() {
+        println!("{}", "The journal is already as in the backup.".yellow());
+        return;
+    }
+    for (rel_path, _, exists) in changed.iter() {
+        if *exists {
+            println!("{} {}", "Overwrite:".yellow(), rel_path);
+        } else {
+            println!("{} {}", "Restore:".green(), rel_path);
+        }
+    }
+    println!(
+        "\n{} file(s) will be restored.",
+        changed.len().to_string().bold()
+    );
+    if !inquire_confirm("Restore these files?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Taken only now, so that other `jrnl`s are not blocked while waiting for the user.
+    // The files are overwritten with the backup anyway.
+    let _lock = lock_journal();
+    backup_before_rewrite();
+    for (rel_path, contents, _) in changed {
+        write_restored(&rel_path, &contents);
+    }
+    println!("{}", "Done.".green());
+}
+
+/// Returns the entries of the span in the backup, as (date, lines of each entry of the date).
+/// The journal files are found by the configured `layout`, or any of the built-in ones, in
+/// case the backup was made before a `jrnl migrate`.
+fn get_archived_entries(
+    files: &[(String, Vec<u8>)],
+    span: &DateSpan,
+) -> BTreeMap<NaiveDate, Vec<Vec<String>>> {
+    let patterns = [config_layout(), MONTHLY.into(), DAILY.into(), YEARLY.into()];
+    let mut entries: BTreeMap<NaiveDate, Vec<Vec<String>>> = BTreeMap::new();
+    for (rel_path, contents) in files {
+        if !patterns
+            .iter()
+            .any(|pattern| match_pattern(pattern, rel_path).is_some())
+        {
+            continue;
+        }
+        let Ok(text) = std::str::from_utf8(contents) else {
+            continue;
+        };
+        for (date, lines) in split_entries(text) {
+            if let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                && date >= span.start
+                && date <= span.end
+            {
+                let new_path = journal_path(&config_layout(), date);
+                let lines = relink_attachments(&lines, rel_path, &new_path);
+                entries.entry(date).or_default().push(lines);
+            }
+        }
+    }
+    entries
+}
+
+/// Restores only the entries of the span, into the journal files. Entries already in the
+/// journal are replaced(all of them, if a date has more than one), and the others are
+/// added in date order. The attachments linked
+/// from them are restored too, if they are missing.
+fn restore_entries(archive: &str, span: &DateSpan) {
+    let files = read_archive(archive);
+    let entries = get_archived_entries(&files, span);
+    if entries.is_empty() {
+        eprintln!(
+            "{}",
+            format!(
+                "There are no entries from {} to {} in the backup.",
+                span.start.format("%Y-%m-%d"),
+                span.end.format("%Y-%m-%d")
+            )
+            .red()
+        );
+        process::exit(1);
+    }
+
+    let root = format!("{}/jrnl_folder", get_default_path());
+    // Journal file -> its entries after the restore
+    let mut changed_files: BTreeMap<String, Vec<(String, Vec<String>)>> = BTreeMap::new();
+    let mut attachments: Vec<String> = Vec::new();
+    let mut touched: HashSet<String> = HashSet::new();
+    // (filename, contents), to check that they are unchanged when writing
+    let mut read_files: Vec<(String, String)> = Vec::new();
+    let mut no_of_changes = 0;
+    for (date, archived) in entries {
+        let filename = format!("{}/{}", root, journal_path(&config_layout(), date));
+        let file_entries = changed_files.entry(filename.clone()).or_insert_with(|| {
+            let contents = fs::read_to_string(&filename).unwrap_or_default();
+            read_files.push((filename.clone(), contents.clone()));
+            split_entries(&contents)
+        });
+        let date_str = date.format("%Y-%m-%d").to_string();
+        let current: Vec<&Vec<String>> = file_entries
+            .iter()
+            .filter(|(d, _)| *d == date_str)
+            .map(|(_, lines)| lines)
+            .collect();
+        if current.iter().copied().eq(archived.iter()) {
+            continue;
+        }
+        // Where the entries of the date go: in place of the current ones, or in date order
+        let i = if current.is_empty() {
+            println!("{} {}", "Restore:".green(), date_str.cyan());
+            file_entries
+                .iter()
+                .position(|(d, _)| *d > date_str)
+                .unwrap_or(file_entries.len())
+        } else {
+            println!("{} {}", "Replace:".yellow(), date_str.cyan());
+            let i = file_entries
+                .iter()
+                .position(|(d, _)| *d == date_str)
+                .unwrap();
+            file_entries.retain(|(d, _)| *d != date_str);
+            i
+        };
+        for (n, lines) in archived.iter().enumerate() {
+            file_entries.insert(i + n, (date_str.clone(), lines.clone()));
+        }
+        no_of_changes += 1;
+        touched.insert(filename);
+        for line in archived.iter().flatten() {
+            for attachment in get_attachments_from_line(line) {
+                if !Path::new(&format!("{}/{}", root, attachment)).exists() {
+                    attachments.push(attachment);
+                }
+            }
+        }
+    }
+    if no_of_changes == 0 {
+        println!("{}", "These entries are already as in the backup.".yellow());
+        return;
+    }
+    attachments.sort();
+    attachments.dedup();
+    let attachments: Vec<&(String, Vec<u8>)> = files
+        .iter()
+        .filter(|(rel_path, _)| attachments.contains(rel_path))
+        .collect();
+    for (rel_path, _) in attachments.iter() {
+        println!("{} {}", "Restore:".green(), rel_path);
+    }
+    println!(
+        "\n{} entries will be restored.",
+        no_of_changes.to_string().bold()
+    );
+    if !inquire_confirm("Restore these entries?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Held until everything is written, so that no change made meanwhile is lost
+    let _lock = lock_unchanged(&read_files);
+    backup_before_rewrite();
+    for (filename, file_entries) in changed_files {
+        // Only read to compare the entries
+        if !touched.contains(&filename) {
+            continue;
+        }
+        let mut contents = String::new();
+        for (_, lines) in file_entries {
+            contents.push_str(&format!("\n{}\n", lines.join("\n")));
+        }
+        if let Some(parent) = Path::new(&filename).parent()
+            && let Err(e) = fs::create_dir_all(parent)
+        {
+            panic!("An error occured: {}", e);
+        }
+        if let Err(e) = write_journal(&filename, &contents) {
+            panic!("An error occured: {}", e);
+        }
+    }
+    for (rel_path, contents) in attachments {
+        write_restored(rel_path, contents);
+    }
+    println!("{}", "Done.".green());
+}
+
+/// Restores the journal from a backup: everything, or only the entries of the dates
+/// (a span like `2026-10`, or a range like `2026-10-01..2026-10-07`).
+pub fn restore(archive: &str, date: Option<&str>) {
+    match date {
+        None => restore_everything(archive),
+        Some(date) => {
+            let span = match date.split_once("..") {
+                Some((from, to)) => parse_range(from, Some(to)),
+                None => parse_span(date),
+            };
+            restore_entries(archive, &span);
+        }
+    }
+}
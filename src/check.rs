@@ -0,0 +1,91 @@
+//! Contains `jrnl check`, which looks for problems in the journal.
+//!
+//! These are:
+//! - wiki links(`[[2026-10-01]]`, `[[projects/alpha]]`) pointing to an entry or a note which
+//!   does not exist
+//! - links to attachments which do not exist
+//! - attachments which are not linked from any entry or note
+use crate::attachments::{get_missing_attachments, get_orphaned_attachments};
+use crate::funcs::*;
+use crate::links::get_dangling_links;
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use std::process;
+
+/// Prints all the problems found in the journal, and exits with an error if there are any.
+///
+/// # Sample output:
+/// Note that the headings are colored in green.
+///
+/// ```text
+/// ╭───────────────┬────────────┬──────┬───────────────────╮
+/// │ Problem       ┆ Where      ┆ Line ┆ Details           │
+/// ╞═══════════════╪════════════╪══════╪═══════════════════╡
+/// │ Dangling link ┆ 2026-10-03 ┆ 42   ┆ [[2026-10-02]]    │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ Dangling link ┆ ideas.md   ┆ 7    ┆ [[projects/beta]] │
+/// ╰───────────────┴────────────┴──────┴───────────────────╯
+/// 2 problem(s) found.
+/// ```
+pub fn run_check() {
+    // (problem, where, line number, details)
+    let mut problems: Vec<(String, String, usize, String)> = Vec::new();
+    for link in get_dangling_links() {
+        problems.push((
+            String::from("Dangling link"),
+            link.source,
+            link.line_no,
+            format!("[[{}]]", link.target),
+        ));
+    }
+    for link in get_missing_attachments() {
+        problems.push((
+            String::from("Missing attachment"),
+            link.source,
+            link.line_no,
+            link.target,
+        ));
+    }
+    for attachment in get_orphaned_attachments() {
+        problems.push((
+            String::from("Orphaned attachment"),
+            attachment,
+            0,
+            String::from("Not linked from any entry or note"),
+        ));
+    }
+
+    if problems.is_empty() {
+        println!("{}", "No problems found.".green());
+        return;
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Problem".green(),
+            "Where".green(),
+            "Line".green(),
+            "Details".green(),
+        ]);
+    for (problem, place, line_no, details) in problems.iter() {
+        table.add_row(vec![
+            problem.red().to_string(),
+            place.cyan().to_string(),
+            // The problems with a whole file have no line
+            if *line_no == 0 {
+                String::new()
+            } else {
+                line_no.to_string()
+            },
+            details.clone(),
+        ]);
+    }
+    print_with_pager(
+        &format!("{}\n{} problem(s) found.", table, problems.len()),
+        problems.len(),
+    );
+    process::exit(1);
+}
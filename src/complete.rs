@@ -0,0 +1,181 @@
+//! Contains the shell completions(`jrnl completions <shell>`) and the man page(`jrnl man`),
+//! both generated from the [`Cli`][crate::Cli] definition.
+//!
+//! The completions are dynamic: the shell calls `jrnl` itself to complete tags,
+//! notes, and dates, so they always match what is in the journal.
+use crate::utils::*;
+use clap::CommandFactory;
+use clap_complete::{
+    engine::CompletionCandidate,
+    env::{Bash, Elvish, EnvCompleter, Fish, Powershell, Shells, Zsh},
+};
+use colored::Colorize;
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::Path,
+    process,
+};
+
+/// The environment variable used by the shell to ask `jrnl` for completions.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Returns the candidates which start with what has been typed so far.
+fn filter_candidates(current: &OsStr, values: Vec<String>) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    values
+        .into_iter()
+        .filter(|value| value.starts_with(current.as_ref()))
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Completes tags, from all the tags in the journal(most used first).
+pub fn complete_tags(current: &OsStr) -> Vec<CompletionCandidate> {
+    let tags: Vec<String> = crate::tags::get_tag_usage("count")
+        .into_iter()
+        .map(|usage| usage.tag)
+        .collect();
+    filter_candidates(current, tags)
+}
+
+/// Completes notes, from the files in `jrnl_folder`.
+pub fn complete_notes(current: &OsStr) -> Vec<CompletionCandidate> {
+    filter_candidates(current, get_note_files())
+}
+
+/// Completes dates, from the latest entries, and some relative phrases.
+pub fn complete_dates(current: &OsStr) -> Vec<CompletionCandidate> {
+    let mut dates: Vec<String> = get_all_entries()
+        .into_iter()
+        .rev()
+        .take(30)
+        .map(|(date, _)| date)
+        .collect();
+    let today = crate::dates::journal_today().format("%Y-%m-%d").to_string();
+    if !dates.contains(&today) {
+        dates.push(today);
+    }
+    for phrase in ["today", "yesterday", "tomorrow", "last week"] {
+        dates.push(phrase.to_string());
+    }
+    for weekday in [
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+        "sunday",
+    ] {
+        dates.push(format!("last {}", weekday));
+    }
+    for days in [2, 3, 7] {
+        dates.push(format!("{} days ago", days));
+    }
+    // The shell splits words on spaces, so the phrases must be typed in quotes.
+    filter_candidates(current, dates)
+}
+
+/// Nushell, which clap_complete has no dynamic completions for. Registered as nushell's
+/// external completer, which asks `jrnl` for the completions like the other shells do.
+pub struct Nushell;
+
+impl EnvCompleter for Nushell {
+    fn name(&self) -> &'static str {
+        "nushell"
+    }
+
+    fn is(&self, name: &str) -> bool {
+        name == "nushell" || name == "nu"
+    }
+
+    fn write_registration(
+        &self,
+        var: &str,
+        _name: &str,
+        bin: &str,
+        completer: &str,
+        buf: &mut dyn io::Write,
+    ) -> Result<(), io::Error> {
+        // Any external completer set before is kept for the other commands
+        let script = r#"
+let jrnl_completer = {|spans: list<string>|
+    VAR=nushell ^"COMPLETER" -- ...$spans
+    | lines
+    | each {|line|
+        let parts = ($line | split row "\t")
+        {value: ($parts | first), description: ($parts | skip 1 | str join " ")}
+    }
+}
+
+let previous_completer = $env.config.completions.external.completer?
+$env.config.completions.external.enable = true
+$env.config.completions.external.completer = {|spans: list<string>|
+    if ($spans | first) == "BIN" {
+        do $jrnl_completer $spans
+    } else if $previous_completer != null {
+        do $previous_completer $spans
+    }
+}
+"#
+        .replace("VAR", var)
+        .replace("COMPLETER", completer)
+        .replace("BIN", bin);
+        writeln!(buf, "{}", script)
+    }
+
+    fn write_complete(
+        &self,
+        cmd: &mut clap::Command,
+        args: Vec<OsString>,
+        current_dir: Option<&Path>,
+        buf: &mut dyn io::Write,
+    ) -> Result<(), io::Error> {
+        let index = args.len() - 1;
+        for candidate in clap_complete::engine::complete(cmd, args, index, current_dir)? {
+            write!(buf, "{}", candidate.get_value().to_string_lossy())?;
+            if let Some(help) = candidate.get_help() {
+                let help = help.to_string();
+                write!(buf, "\t{}", help.lines().next().unwrap_or_default())?;
+            }
+            writeln!(buf)?;
+        }
+        Ok(())
+    }
+}
+
+/// The shells with completions: the ones of clap_complete, and nushell.
+pub const SHELLS: Shells = Shells(&[&Bash, &Elvish, &Fish, &Powershell, &Zsh, &Nushell]);
+
+/// Prints the script to register the completions for the given shell.
+///
+/// The completions are dynamic for every shell: the script calls `jrnl` to complete.
+pub fn print_completions(shell: &str) {
+    let mut stdout = io::stdout();
+    let completer: &dyn EnvCompleter = match SHELLS.completer(shell) {
+        Some(c) => c,
+        None => {
+            eprintln!(
+                "{}",
+                format!("Completions are not available for {}", shell).red()
+            );
+            process::exit(1);
+        }
+    };
+    match completer.write_registration(COMPLETE_VAR, "jrnl", "jrnl", "jrnl", &mut stdout) {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
+    }
+}
+
+/// Prints the man page, in the roff format.
+///
+/// Can be viewed with `jrnl man | man -l -`
+pub fn print_man() {
+    let man = clap_mangen::Man::new(crate::Cli::command());
+    match man.render(&mut io::stdout()) {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
+    }
+}
@@ -0,0 +1,553 @@
+//! Contains the date-resolution used by every flag that takes a date, a month or a year.
+//!
+//! All of these understand the same inputs:
+//! - `YYYY-MM-DD`, `YYYY-MM`, `YYYY`
+//! - ISO weeks(`2026-W42`) and quarters(`2025-Q3`)
+//! - month names(`march`, `mar`, `march 2025`)
+//! - `this`/`last`/`next` + `week`/`month`/`quarter`/`year`
+//! - anything understood by [parse_datetime](https://docs.rs/parse_datetime/latest/parse_datetime/),
+//!   like `yesterday`, `last friday` or `3 days ago`
+//!
+//! "Today" is the journal's today([`journal_today`]): it is taken in the configured `timezone`,
+//! and does not change until `day_starts_at`.
+use crate::funcs::read_config;
+use chrono::{
+    DateTime, Datelike, Duration, Local, Month, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use colored::Colorize;
+use parse_datetime::parse_datetime_at_date as pdad;
+use std::process;
+use stringmetrics::levenshtein;
+
+/// How long a [`DateSpan`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+
+    /// Any range, like `--from 2026-03-15 --to 2026-06-30`
+    Range,
+}
+
+/// A range of dates(both ends included), as understood from the user's input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateSpan {
+    /// First day of the span
+    pub start: NaiveDate,
+
+    /// Last day of the span
+    pub end: NaiveDate,
+
+    /// Whether this is a day, a week, etc.
+    pub kind: SpanKind,
+}
+
+impl DateSpan {
+    fn day(date: NaiveDate) -> DateSpan {
+        DateSpan {
+            start: date,
+            end: date,
+            kind: SpanKind::Day,
+        }
+    }
+
+    fn week(first_day: NaiveDate) -> DateSpan {
+        DateSpan {
+            start: first_day,
+            end: first_day + Duration::days(6),
+            kind: SpanKind::Week,
+        }
+    }
+
+    fn months(year: i32, month: u32, no_of_months: u32, kind: SpanKind) -> Option<DateSpan> {
+        let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let end = (start + Months::new(no_of_months)).pred_opt()?;
+        Some(DateSpan { start, end, kind })
+    }
+
+    /// The whole month, or `None` if the month is not valid.
+    pub fn month(year: i32, month: u32) -> Option<DateSpan> {
+        DateSpan::months(year, month, 1, SpanKind::Month)
+    }
+
+    /// The whole year.
+    pub fn year(year: i32) -> DateSpan {
+        DateSpan::months(year, 1, 12, SpanKind::Year).unwrap()
+    }
+
+    /// Every day from `start` to `end`.
+    pub fn range(start: NaiveDate, end: NaiveDate) -> DateSpan {
+        DateSpan {
+            start,
+            end,
+            kind: SpanKind::Range,
+        }
+    }
+
+    /// The span of the same kind right before this one: the previous month for a month,
+    /// and the same number of days right before for a range.
+    pub fn previous(&self) -> DateSpan {
+        let no_of_months = match self.kind {
+            SpanKind::Month => 1,
+            SpanKind::Quarter => 3,
+            SpanKind::Year => 12,
+            SpanKind::Day | SpanKind::Week | SpanKind::Range => {
+                let days = (self.end - self.start).num_days() + 1;
+                return DateSpan {
+                    start: self.start - Duration::days(days),
+                    end: self.start - Duration::days(1),
+                    kind: self.kind,
+                };
+            }
+        };
+        let start = shift_months(self.start, -no_of_months);
+        DateSpan::months(start.year(), start.month(), no_of_months as u32, self.kind).unwrap()
+    }
+}
+
+/// Words understood in the inputs, used to suggest corrections for typos.
+const KNOWN_WORDS: [&str; 39] = [
+    "today",
+    "yesterday",
+    "tomorrow",
+    "this",
+    "last",
+    "next",
+    "ago",
+    "day",
+    "days",
+    "week",
+    "weeks",
+    "month",
+    "months",
+    "quarter",
+    "year",
+    "years",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+    "fortnight",
+    "hour",
+    "hours",
+    "now",
+];
+
+/// Returns the configured `timezone`, or `None` if it is not set(or not a valid timezone),
+/// in which case the local timezone is used.
+pub fn config_timezone() -> Option<Tz> {
+    read_config().0.timezone.parse::<Tz>().ok()
+}
+
+/// Returns the configured `day_starts_at`, or midnight if it is not set(or not a valid time).
+pub fn config_day_start() -> NaiveTime {
+    NaiveTime::parse_from_str(&read_config().0.day_starts_at, "%H:%M").unwrap_or(NaiveTime::MIN)
+}
+
+/// Returns the configured `week_start`: Sunday if set to "sunday", otherwise Monday.
+pub fn config_week_start() -> Weekday {
+    match read_config().0.week_start.to_lowercase().as_str() {
+        "sunday" | "sun" => Weekday::Sun,
+        _ => Weekday::Mon,
+    }
+}
+
+/// Returns the position(starting at 0) of the weekday in the week, as per `week_start`.
+pub fn days_from_week_start(weekday: Weekday) -> u32 {
+    (weekday.num_days_from_monday() + 7 - config_week_start().num_days_from_monday()) % 7
+}
+
+/// Returns the first day of the week containing the date, as per `week_start`.
+pub fn start_of_week(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(days_from_week_start(date.weekday()) as i64)
+}
+
+/// Returns the short names of the weekdays, in order from `week_start`,
+/// like "Mo Tu We Th Fr Sa Su".
+pub fn weekdays_header() -> String {
+    let mut weekday = config_week_start();
+    let mut names: Vec<String> = Vec::new();
+    for _ in 0..7 {
+        names.push(weekday.to_string()[..2].to_string());
+        weekday = weekday.succ();
+    }
+    names.join(" ")
+}
+
+/// Returns the current date and time, as shown by a clock in the configured `timezone`.
+pub fn journal_now() -> NaiveDateTime {
+    journal_datetime(Utc::now())
+}
+
+/// Returns the date and time as shown by a clock in the configured `timezone`.
+pub fn journal_datetime(datetime: DateTime<Utc>) -> NaiveDateTime {
+    match config_timezone() {
+        Some(tz) => datetime.with_timezone(&tz).naive_local(),
+        None => datetime.with_timezone(&Local).naive_local(),
+    }
+}
+
+/// Returns the journal's today.
+///
+/// Before `day_starts_at`, this is still the previous date, so that writing at 01:30 with
+/// `day_starts_at = "04:00"` goes into the previous day's entry.
+pub fn journal_today() -> NaiveDate {
+    journal_date(journal_now())
+}
+
+/// Returns the journal's date at the given date and time, that is, the previous date
+/// if it is before `day_starts_at`.
+pub fn journal_date(datetime: NaiveDateTime) -> NaiveDate {
+    (datetime - config_day_start().signed_duration_since(NaiveTime::MIN)).date()
+}
+
+/// Returns the month, if the word is a month's name(or its first three letters).
+fn month_from_name(word: &str) -> Option<u32> {
+    let month = word.parse::<Month>().ok()?;
+    Some(month.number_from_month())
+}
+
+/// Understands the input as a span of dates, relative to `today`.
+///
+/// Returns an error message if the input cannot be understood.
+///
+/// ## Example:
+/// ```
+/// let span = resolve_span("2026-W42", journal_today()).unwrap();
+/// assert_eq!(span.start, NaiveDate::from_ymd_opt(2026, 10, 12).unwrap());
+/// assert_eq!(span.kind, SpanKind::Week);
+/// ```
+pub fn resolve_span(input: &str, today_naive: NaiveDate) -> Result<DateSpan, String> {
+    let input = input.trim().to_lowercase();
+    let parts: Vec<&str> = input.split('-').collect();
+
+    // Exact formats first
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Ok(DateSpan::day(date));
+    }
+    if parts.len() == 2
+        && parts[0].len() == 4
+        && let Ok(year) = parts[0].parse::<i32>()
+    {
+        if let Some(week) = parts[1].strip_prefix('w') {
+            let week: u32 = week.parse().map_err(|_| "Invalid week".to_string())?;
+            return match NaiveDate::from_isoywd_opt(year, week, Weekday::Mon) {
+                Some(monday) => Ok(DateSpan::week(monday)),
+                None => Err(format!("{} does not have a week {}", year, week)),
+            };
+        }
+        if let Some(quarter) = parts[1].strip_prefix('q') {
+            return match quarter.parse::<u32>() {
+                Ok(q) if (1..=4).contains(&q) => {
+                    Ok(DateSpan::months(year, (q - 1) * 3 + 1, 3, SpanKind::Quarter).unwrap())
+                }
+                _ => Err("A quarter must be between Q1 and Q4".to_string()),
+            };
+        }
+        if let Ok(month) = parts[1].parse::<u32>() {
+            return DateSpan::months(year, month, 1, SpanKind::Month)
+                .ok_or_else(|| format!("{} is not a valid month", month));
+        }
+    }
+    if input.len() == 4
+        && let Ok(year) = input.parse::<i32>()
+    {
+        return Ok(DateSpan::months(year, 1, 12, SpanKind::Year).unwrap());
+    }
+
+    // Weekdays: `friday`(this week's), `last friday`, `next friday`
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let (offset, weekday) = match words.as_slice() {
+        [day] => (0, day.parse::<Weekday>().ok()),
+        ["this", day] => (0, day.parse::<Weekday>().ok()),
+        ["last", day] => (-1, day.parse::<Weekday>().ok()),
+        ["next", day] => (1, day.parse::<Weekday>().ok()),
+        _ => (0, None),
+    };
+    if let Some(weekday) = weekday {
+        let mut date = today_naive;
+        match offset {
+            -1 => {
+                date = date.pred_opt().unwrap();
+                while date.weekday() != weekday {
+                    date = date.pred_opt().unwrap();
+                }
+            }
+            1 => {
+                date = date.succ_opt().unwrap();
+                while date.weekday() != weekday {
+                    date = date.succ_opt().unwrap();
+                }
+            }
+            _ => {
+                date = start_of_week(date) + Duration::days(days_from_week_start(weekday) as i64);
+            }
+        }
+        return Ok(DateSpan::day(date));
+    }
+
+    // this/last/next week/month/quarter/year
+    if words.len() == 2 {
+        let offset: i32 = match words[0] {
+            "this" => 0,
+            "last" => -1,
+            "next" => 1,
+            _ => 2,
+        };
+        if offset != 2 {
+            let span = match words[1] {
+                "week" => {
+                    let first_day = start_of_week(today_naive);
+                    Some(DateSpan::week(first_day + Duration::weeks(offset as i64)))
+                }
+                "month" => {
+                    let this_month = DateSpan::months(
+                        today_naive.year(),
+                        today_naive.month(),
+                        1,
+                        SpanKind::Month,
+                    )
+                    .unwrap();
+                    let start = shift_months(this_month.start, offset);
+                    DateSpan::months(start.year(), start.month(), 1, SpanKind::Month)
+                }
+                "quarter" => {
+                    let q_start = (today_naive.month() - 1) / 3 * 3 + 1;
+                    let start = shift_months(
+                        NaiveDate::from_ymd_opt(today_naive.year(), q_start, 1).unwrap(),
+                        offset * 3,
+                    );
+                    DateSpan::months(start.year(), start.month(), 3, SpanKind::Quarter)
+                }
+                "year" => DateSpan::months(today_naive.year() + offset, 1, 12, SpanKind::Year),
+                _ => None,
+            };
+            if let Some(span) = span {
+                return Ok(span);
+            }
+        }
+    }
+
+    // Month names, optionally with a year
+    if let Some(month) = words.first().and_then(|w| month_from_name(w)) {
+        let year = match words.get(1) {
+            None => Some(today_naive.year()),
+            Some(y) if words.len() == 2 => y.parse::<i32>().ok(),
+            _ => None,
+        };
+        if let Some(year) = year {
+            return Ok(DateSpan::months(year, month, 1, SpanKind::Month).unwrap());
+        }
+    }
+
+    // Anything else relative(yesterday, last friday, 3 days ago, etc).
+    // Relative to noon, so that neither the timezone nor `day_starts_at` can move it to
+    // another date, and the date is taken as is, not in UTC.
+    let noon = today_naive.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+    let today = Local
+        .from_local_datetime(&noon)
+        .earliest()
+        .unwrap_or_else(Local::now);
+    match pdad(today, &input) {
+        Ok(value) => Ok(DateSpan::day(value.date_naive())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Moves the date by some months, backwards if negative.
+fn shift_months(date: NaiveDate, months: i32) -> NaiveDate {
+    if months >= 0 {
+        date + Months::new(months as u32)
+    } else {
+        date - Months::new(-months as u32)
+    }
+}
+
+/// Prints why the input could not be understood, along with suggestions, and exits.
+fn exit_with_suggestions(input: &str, error: &str, examples: &str) -> ! {
+    eprintln!(
+        "{}: Couldn't understand the date '{}': {}",
+        "ERROR".red().bold(),
+        input.yellow(),
+        error
+    );
+    let mut suggestions: Vec<String> = Vec::new();
+    for word in input.to_lowercase().split(&[' ', '-'][..]) {
+        if word.is_empty() || word.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        if KNOWN_WORDS.contains(&word) {
+            continue;
+        }
+        if let Some(best) = KNOWN_WORDS
+            .iter()
+            .filter(|known| levenshtein(word, known) <= 2)
+            .min_by_key(|known| levenshtein(word, known))
+        {
+            suggestions.push(input.to_lowercase().replacen(word, best, 1));
+        }
+    }
+    if !suggestions.is_empty() {
+        eprintln!(
+            "{}: Did you mean '{}'?",
+            "HELP".green().bold(),
+            suggestions.join("' or '")
+        );
+    }
+    eprintln!("{}: Try something like {}", "HELP".green().bold(), examples);
+    process::exit(1);
+}
+
+/// Returns the span meant by the input(a day, week, month, quarter or year),
+/// or exits with suggestions.
+pub fn parse_span(input: &str) -> DateSpan {
+    match resolve_span(input, journal_today()) {
+        Ok(span) => span,
+        Err(e) => exit_with_suggestions(
+            input,
+            &e,
+            "2026-09, march, 2026-W42, 2026-Q3, 2025 or \"last month\"",
+        ),
+    }
+}
+
+/// Returns the date meant by the input, or exits with suggestions.
+///
+/// For inputs meaning more than a day(`2026-W42`, `march`), the first day is used.
+pub fn parse_day(input: &str) -> NaiveDate {
+    match resolve_span(input, journal_today()) {
+        Ok(span) => span.start,
+        Err(e) => exit_with_suggestions(
+            input,
+            &e,
+            "2025-03-27, yesterday, \"last friday\", \"3 days ago\" or 2026-W42",
+        ),
+    }
+}
+
+/// Returns the month meant by the input as (year, month), or exits with suggestions.
+///
+/// The year is `None` if only the month was given(`3`, `march`), so that it can be
+/// taken from `-y`.
+pub fn parse_month(input: &str) -> (Option<i32>, u32) {
+    if let Ok(month) = input.trim().parse::<u32>() {
+        if (1..=12).contains(&month) {
+            return (None, month);
+        }
+        exit_with_suggestions(
+            input,
+            &format!("{} is not a valid month", month),
+            "3, march, 2025-03, \"last month\" or \"2 months ago\"",
+        );
+    }
+    if let Some(month) = month_from_name(input.trim()) {
+        return (None, month);
+    }
+    match resolve_span(input, journal_today()) {
+        Ok(span) => (Some(span.start.year()), span.start.month()),
+        Err(e) => exit_with_suggestions(
+            input,
+            &e,
+            "3, march, 2025-03, \"last month\" or \"2 months ago\"",
+        ),
+    }
+}
+
+/// Returns the week meant by the input, or exits with suggestions.
+///
+/// A single day(`2026-10-14`, `yesterday`) means the week containing it, and
+/// `this`, `last` and `next` alone mean the week. ISO weeks(`2026-W42`) always start on Monday,
+/// other weeks start on `week_start`.
+pub fn parse_week(input: &str) -> DateSpan {
+    let input = match input.trim().to_lowercase().as_str() {
+        word @ ("this" | "last" | "next") => format!("{} week", word),
+        _ => input.to_string(),
+    };
+    let examples = "2026-W42, last, \"2 weeks ago\" or 2026-10-14";
+    match resolve_span(&input, journal_today()) {
+        Ok(span) if span.kind == SpanKind::Week => span,
+        Ok(span) if span.kind == SpanKind::Day => DateSpan::week(start_of_week(span.start)),
+        Ok(_) => exit_with_suggestions(&input, "This is longer than a week", examples),
+        Err(e) => exit_with_suggestions(&input, &e, examples),
+    }
+}
+
+/// Returns the quarter meant by the input, or exits with suggestions.
+///
+/// A day, week or month means the quarter containing it, and
+/// `this`, `last` and `next` alone mean the quarter.
+pub fn parse_quarter(input: &str) -> DateSpan {
+    let input = match input.trim().to_lowercase().as_str() {
+        word @ ("this" | "last" | "next") => format!("{} quarter", word),
+        _ => input.to_string(),
+    };
+    let examples = "2026-Q2, last, \"next quarter\" or 2026-05";
+    match resolve_span(&input, journal_today()) {
+        Ok(span) if span.kind == SpanKind::Year => {
+            exit_with_suggestions(&input, "This is longer than a quarter", examples)
+        }
+        Ok(span) => {
+            let q_start = (span.start.month() - 1) / 3 * 3 + 1;
+            DateSpan::months(span.start.year(), q_start, 3, SpanKind::Quarter).unwrap()
+        }
+        Err(e) => exit_with_suggestions(&input, &e, examples),
+    }
+}
+
+/// Returns the range of dates from the start of `from` to the end of `to`(today if not given),
+/// or exits with suggestions.
+///
+/// So `--from march --to april` means 1st March to 30th April.
+pub fn parse_range(from: &str, to: Option<&str>) -> DateSpan {
+    let examples = "2026-03-15, march, 2026-Q1 or \"last month\"";
+    let today = journal_today();
+    let start = match resolve_span(from, today) {
+        Ok(span) => span.start,
+        Err(e) => exit_with_suggestions(from, &e, examples),
+    };
+    let end = match to {
+        None => today,
+        Some(to) => match resolve_span(to, today) {
+            Ok(span) => span.end,
+            Err(e) => exit_with_suggestions(to, &e, examples),
+        },
+    };
+    if end < start {
+        eprintln!(
+            "{}: The range ends({}) before it starts({})",
+            "ERROR".red().bold(),
+            end.format("%Y-%m-%d").to_string().yellow(),
+            start.format("%Y-%m-%d").to_string().yellow()
+        );
+        process::exit(1);
+    }
+    DateSpan::range(start, end)
+}
+
+/// Returns the year meant by the input, or exits with suggestions.
+pub fn parse_year(input: &str) -> i32 {
+    match resolve_span(input, journal_today()) {
+        Ok(span) => span.start.year(),
+        Err(e) => exit_with_suggestions(input, &e, "2025, \"last year\" or 2025-Q3"),
+    }
+}
@@ -0,0 +1,76 @@
+//! Contains `jrnl export`, which copies the entries of a span into one Markdown file,
+//! along with their attachments, so that the links in it keep working:
+//! ```text
+//! export/
+//! ├── jrnl_2026-10-01_2026-10-19.md
+//! └── attachments/2026/10/3f2a9c1b7d4e8a06.jpg
+//! ```
+use crate::attachments::{copy_attachments, get_attachments_from_line};
+use crate::dates::DateSpan;
+use crate::get_default_path;
+use crate::layout::{config_layout, journal_path, read_entries_in, relink_attachments};
+use colored::Colorize;
+use std::{fs, path::Path, process};
+
+/// Writes the entries of the span into a Markdown file in the folder, and copies the
+/// attachments they link to into its `attachments/`.
+///
+/// The links to attachments are made relative to the exported file. Attachments which do
+/// not exist(see `jrnl check`) are left out.
+pub fn export_entries(span: &DateSpan, folder: &str) {
+    let root = format!("{}/jrnl_folder", get_default_path());
+    let pattern = config_layout();
+    let entries = read_entries_in(&root, &pattern);
+
+    let mut contents = String::new();
+    let mut count = 0;
+    let mut attachments: Vec<String> = Vec::new();
+    for (date, date_entries) in entries.range(span.start..=span.end) {
+        let path = journal_path(&pattern, *date);
+        for lines in date_entries {
+            // The exported file is at the top of the folder
+            let lines = relink_attachments(lines, &path, "export.md");
+            for line in lines.iter() {
+                attachments.extend(get_attachments_from_line(line));
+            }
+            contents.push_str(&format!("\n{}\n", lines.join("\n")));
+            count += 1;
+        }
+    }
+    if count == 0 {
+        eprintln!(
+            "{}",
+            format!(
+                "No entries found from {} to {}.",
+                span.start.format("%Y-%m-%d"),
+                span.end.format("%Y-%m-%d")
+            )
+            .red()
+        );
+        process::exit(1);
+    }
+    attachments.sort();
+    attachments.dedup();
+    attachments.retain(|attachment| Path::new(&format!("{}/{}", root, attachment)).exists());
+
+    let folder = shellexpand::tilde(folder).into_owned();
+    if let Err(e) = fs::create_dir_all(&folder) {
+        panic!("An error occured: {}", e);
+    }
+    let file = format!(
+        "jrnl_{}_{}.md",
+        span.start.format("%Y-%m-%d"),
+        span.end.format("%Y-%m-%d")
+    );
+    if let Err(e) = fs::write(format!("{}/{}", folder, file), contents.trim_start()) {
+        panic!("An error occured: {}", e);
+    }
+    copy_attachments(&root, &folder, &attachments);
+    println!(
+        "{} {} entries and {} attachments to {}",
+        "Exported".green().bold(),
+        count,
+        attachments.len(),
+        format!("{}/{}", folder, file).bold()
+    );
+}
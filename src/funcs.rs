@@ -2,14 +2,15 @@
 
 //! This contains _small_ functions that are used in [`utils`][crate::utils]
 //! Some of them are directly used in [`main`][crate::main]
-use chrono::{Datelike, Month, NaiveDate};
+use chrono::{Datelike, Month, Months, NaiveDate};
 use colored::Colorize;
 use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
-use inquire::DateSelect;
+use inquire::{Confirm, DateSelect, Select, Text};
 use pager::Pager;
 use std::{
+    collections::HashMap,
     fs::{self, File},
-    io::ErrorKind,
+    io::{ErrorKind, Write},
     path::Path,
     process,
 };
@@ -33,6 +34,17 @@ use crate::utils::*;
 ///         when_pager: "default".to_string(),
 ///         default_path: String::from("."),
 ///         approx_variation: 1,
+///         carry_over_tasks: false,
+///         timezone: String::new(),
+///         day_starts_at: String::new(),
+///         week_start: String::new(),
+///         on_this_day_in_report: false,
+///         layout: String::new(),
+///         backup_folder: String::new(),
+///         backup_keep_daily: 7,
+///         backup_keep_weekly: 4,
+///         backup_keep_monthly: 12,
+///         backup_before_rewrite: true,
 ///     }
 /// );
 /// ```
@@ -47,6 +59,17 @@ pub fn default_conf() -> Config {
         when_pager: "default".to_string(),
         default_path: String::from("."),
         approx_variation: 1,
+        carry_over_tasks: false,
+        timezone: String::new(),
+        day_starts_at: String::new(),
+        week_start: String::new(),
+        on_this_day_in_report: false,
+        layout: String::new(),
+        backup_folder: String::new(),
+        backup_keep_daily: 7,
+        backup_keep_weekly: 4,
+        backup_keep_monthly: 12,
+        backup_before_rewrite: true,
     }
 }
 
@@ -77,7 +100,7 @@ pub fn check_file_existed(filename: &str) -> bool {
     let path: &Path = Path::new(filename);
 
     if !path.exists() {
-        let file_result = File::create_new(filename.to_string());
+        let file_result = File::create_new(filename);
         match file_result {
             Ok(_) => false,
             Err(e) => match e.kind() {
@@ -100,32 +123,6 @@ pub fn check_file_existed(filename: &str) -> bool {
     }
 }
 
-/// Takes in a number, generally provided from some NaiveDate(converted),
-/// and returns the string. Useful to get the correct file path.
-///
-/// ## Example:
-/// ```
-/// assert_eq!(correct_month_nums(3), String::from("03"));
-/// assert_eq!(correct_month_nums(20), String::from("00"));
-/// ```
-pub fn correct_month_nums(num: u32) -> String {
-    match num {
-        1 => "01".to_string(),
-        2 => "02".to_string(),
-        3 => "03".to_string(),
-        4 => "04".to_string(),
-        5 => "05".to_string(),
-        6 => "06".to_string(),
-        7 => "07".to_string(),
-        8 => "08".to_string(),
-        9 => "09".to_string(),
-        10 => "10".to_string(),
-        11 => "11".to_string(),
-        12 => "12".to_string(),
-        _ => "00".to_string(),
-    }
-}
-
 /// Takes a month number(generally from NaiveDate) and returns
 /// the name of the month. Used for printing purposes.
 ///
@@ -240,8 +237,11 @@ pub fn make_food_table(dates_values: (Vec<String>, Vec<Vec<String>>)) -> Table {
 ///
 /// Uses the [inquire](https://github.com/mikaelmello/inquire) crate
 pub fn inquire_date() -> NaiveDate {
-    let date_prompt = DateSelect::new("Select a date to search for its entry:").prompt();
-    let date = match date_prompt {
+    let date_prompt = DateSelect::new("Select a date to search for its entry:")
+        .with_starting_date(crate::dates::journal_today())
+        .prompt();
+
+    match date_prompt {
         Ok(date) => date,
         Err(e) => match e {
             inquire::InquireError::OperationCanceled => {
@@ -250,8 +250,103 @@ pub fn inquire_date() -> NaiveDate {
             }
             _ => panic!("An error occured: {}", e),
         },
-    };
-    date
+    }
+}
+
+/// A choice shown by [`inquire_select`]: the `label` is shown, and the `value` is returned.
+pub struct Choice {
+    /// What is returned when this is chosen
+    pub value: String,
+
+    /// What is shown to the user
+    pub label: String,
+}
+
+impl std::fmt::Display for Choice {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// Lets the user pick one of the choices, with fuzzy filtering as they type.
+/// Returns the `value` of the chosen one.
+///
+/// Uses the [inquire](https://github.com/mikaelmello/inquire) crate
+pub fn inquire_select(message: &str, choices: Vec<Choice>) -> String {
+    if choices.is_empty() {
+        eprintln!("{}", "There is nothing to choose from.".red());
+        process::exit(1);
+    }
+    let select_prompt = Select::new(message, choices).with_page_size(10).prompt();
+    match select_prompt {
+        Ok(choice) => choice.value,
+        Err(e) => match e {
+            inquire::InquireError::OperationCanceled => {
+                println!("{}", "Cancelling...".red());
+                process::exit(0);
+            }
+            _ => panic!("An error occured: {}", e),
+        },
+    }
+}
+
+/// Inquires the tag in case not provided, from all tags in the journal,
+/// ranked by how often they are used.
+pub fn inquire_tag() -> String {
+    let choices: Vec<Choice> = crate::tags::get_tag_usage("count")
+        .into_iter()
+        .map(|usage| Choice {
+            label: format!("{} ({})", usage.tag, usage.count),
+            value: usage.tag,
+        })
+        .collect();
+    inquire_select("Select a tag:", choices)
+}
+
+/// Inquires the word to be searched for, in case not provided.
+pub fn inquire_search() -> String {
+    let text_prompt = Text::new("Search for:").prompt();
+    match text_prompt {
+        Ok(text) if !text.trim().is_empty() => text.trim().to_string(),
+        Ok(_) => {
+            println!("{}", "Nothing to search for. Cancelling...".red());
+            process::exit(0);
+        }
+        Err(e) => match e {
+            inquire::InquireError::OperationCanceled => {
+                println!("{}", "Cancelling...".red());
+                process::exit(0);
+            }
+            _ => panic!("An error occured: {}", e),
+        },
+    }
+}
+
+/// Inquires the date of an entry, from the dates which actually have an entry(latest first).
+/// The first line of each entry is shown next to its date.
+pub fn inquire_entry() -> NaiveDate {
+    let choices: Vec<Choice> = get_all_entries()
+        .into_iter()
+        .rev()
+        .map(|(date, first_line)| Choice {
+            label: format!("{}  {}", date, first_line.dimmed()),
+            value: date,
+        })
+        .collect();
+    let date = inquire_select("Select an entry:", choices);
+    parse_entry_args(&date)
+}
+
+/// Inquires a note(any file in `jrnl_folder` other than the journal files) to be opened.
+pub fn inquire_note() -> String {
+    let choices: Vec<Choice> = get_note_files()
+        .into_iter()
+        .map(|name| Choice {
+            label: name.clone(),
+            value: name,
+        })
+        .collect();
+    inquire_select("Select a file to open:", choices)
 }
 
 /// Makes a pager to pass some output
@@ -262,6 +357,37 @@ pub fn make_pager(output: &str) {
     println!("{}", output);
 }
 
+/// Prints the output, or passes it to the pager, depending on `when_pager` in the config.
+///
+/// When `when_pager` is "default", the pager is only used if there are at least `max_rows` rows.
+pub fn print_with_pager(output: &str, rows: usize) {
+    let config = read_config().0;
+    if config.when_pager == "always"
+        || (config.when_pager == "default" && rows >= config.max_rows as usize)
+    {
+        make_pager(output);
+    } else {
+        println!("{}", output);
+    }
+}
+
+/// Asks the user for a yes/no confirmation. Defaults to no.
+///
+/// Uses the [inquire](https://github.com/mikaelmello/inquire) crate
+pub fn inquire_confirm(message: &str) -> bool {
+    let confirm_prompt = Confirm::new(message).with_default(false).prompt();
+    match confirm_prompt {
+        Ok(answer) => answer,
+        Err(e) => match e {
+            inquire::InquireError::OperationCanceled => {
+                println!("{}", "Cancelling...".red());
+                process::exit(0);
+            }
+            _ => panic!("An error occured: {}", e),
+        },
+    }
+}
+
 /// Reads the config file and stores the result
 ///
 /// If there is an error, it returns the error message as `(Config, <error message>)`.
@@ -297,6 +423,8 @@ pub fn read_config() -> (Config, String) {
 /// Prints a calendar for the given month, and highlights
 /// certain days with a green, bold modifier.
 ///
+/// The weeks start on `week_start` from the config(Monday below).
+///
 /// # Sample Output:
 /// Note colors cannot be displayed here.
 ///
@@ -313,13 +441,7 @@ pub fn print_calendar(year: i32, month: u32, highlight_day: Vec<u32>) -> String
     let mut output = String::new();
 
     let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-    let last_day = first_day
-        .with_day(1)
-        .unwrap()
-        .with_month(month + 1)
-        .unwrap()
-        .pred_opt()
-        .unwrap();
+    let last_day = (first_day + Months::new(1)).pred_opt().unwrap();
 
     // Print the month and year
     output.push_str(&format!(
@@ -327,12 +449,15 @@ pub fn print_calendar(year: i32, month: u32, highlight_day: Vec<u32>) -> String
         month_no_to_name(month).cyan().bold().underline(),
         year.to_string().cyan().bold().underline()
     ));
-    output.push_str(&format!("{}", "Mo Tu We Th Fr Sa Su\n".bright_yellow()));
+    output.push_str(&format!(
+        "{}\n",
+        crate::dates::weekdays_header().bright_yellow()
+    ));
 
     // Print leading spaces for the first day of the month
-    let first_weekday = first_day.weekday().num_days_from_monday(); // Change to start from Monday
+    let first_weekday = crate::dates::days_from_week_start(first_day.weekday()); // As per `week_start`
     for _ in 0..first_weekday {
-        output.push_str(&format!("   "));
+        output.push_str("   ");
     }
     // Print the days of the month
     for day in first_day.day()..=last_day.day() {
@@ -341,10 +466,130 @@ pub fn print_calendar(year: i32, month: u32, highlight_day: Vec<u32>) -> String
         } else {
             output.push_str(&format!("{:>2} ", day));
         }
-        if (first_weekday + day as u32) % 7 == 0 {
-            output.push_str("\n");
+        if (first_weekday + day).is_multiple_of(7) {
+            output.push('\n');
         }
     }
-    output.push_str("\n"); // New line at the end
+    output.push('\n'); // New line at the end
     output
 }
+
+/// Writes `contents` to `filename` by first writing to a temporary file next to it,
+/// and then renaming it over the original.
+///
+/// This makes sure that the file is never left half-written, even if the program is
+/// killed in the middle of writing. The temporary file is hidden, so that it is never
+/// taken for a note, and the file keeps its permissions.
+pub fn write_atomic(filename: &str, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = Path::new(filename);
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let folder = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    // A unique name(`.2026_10.md.a1B2c3.tmp`), so that two `jrnl`s never write to the same one
+    let prefix = format!(".{}.", name);
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(&prefix).suffix(".tmp");
+    // A new file gets the usual permissions(as per the umask), not those of a temporary file
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(fs::Permissions::from_mode(0o666));
+    }
+    let mut file = builder.tempfile_in(folder)?;
+    file.write_all(contents.as_ref())?;
+    if let Ok(metadata) = fs::metadata(path) {
+        file.as_file().set_permissions(metadata.permissions())?;
+    }
+    file.as_file().sync_all()?;
+    file.persist(filename)?;
+    Ok(())
+}
+
+/// Makes the table of the most used tags, used in the reports.
+///
+/// Nested tags(`[work/projx]`) are rolled up into their parents, so that `work` shows the
+/// total of all its descendants, with the children listed right below it.
+/// Only the top `max_rows` top-level tags(from the config) are shown.
+///
+/// # Sample output:
+/// Note that the headings are colored in green
+///
+/// ```text
+/// ╭───────────┬───────────╮
+/// │ Tag       ┆ Frequency │
+/// ╞═══════════╪═══════════╡
+/// │ work      ┆ 12        │
+/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
+/// │  ├ projx  ┆ 8         │
+/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
+/// │  └ oncall ┆ 4         │
+/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ food      ┆ 6         │
+/// ╰───────────┴───────────╯
+/// ```
+pub fn make_tag_tree_table(freq_map: &HashMap<String, u32>) -> Table {
+    // Frequency of each tag, including all of its descendants
+    let mut rolled_up: HashMap<String, u32> = HashMap::new();
+    for (tag, count) in freq_map.iter() {
+        let parts: Vec<&str> = tag.split('/').collect();
+        for i in 1..=parts.len() {
+            *rolled_up.entry(parts[..i].join("/")).or_insert(0) += count;
+        }
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Tag".green(), "Frequency".green()]);
+
+    let roots = tag_children(&rolled_up, None);
+    for root in roots.iter().take(read_config().0.max_rows as usize) {
+        table.add_row(vec![root.clone(), rolled_up[root].to_string()]);
+        add_tag_tree_rows(&mut table, &rolled_up, root, " ");
+    }
+    table
+}
+
+/// Returns the direct children of `parent`(or the top-level tags if `None`),
+/// sorted by their frequency.
+fn tag_children(rolled_up: &HashMap<String, u32>, parent: Option<&str>) -> Vec<String> {
+    let mut children: Vec<String> = rolled_up
+        .keys()
+        .filter(|tag| match parent {
+            None => !tag.contains('/'),
+            Some(p) => tag
+                .strip_prefix(&format!("{}/", p))
+                .is_some_and(|rest| !rest.contains('/')),
+        })
+        .cloned()
+        .collect();
+    children.sort_by(|a, b| rolled_up[b].cmp(&rolled_up[a]).then(a.cmp(b)));
+    children
+}
+
+/// Adds the rows for all descendants of `parent` to the table, indented as a tree.
+fn add_tag_tree_rows(
+    table: &mut Table,
+    rolled_up: &HashMap<String, u32>,
+    parent: &str,
+    indent: &str,
+) {
+    let children = tag_children(rolled_up, Some(parent));
+    for (i, child) in children.iter().enumerate() {
+        let last = i == children.len() - 1;
+        let name = child.rsplit('/').next().unwrap_or(child);
+        table.add_row(vec![
+            format!("{}{} {}", indent, if last { "└" } else { "├" }, name),
+            rolled_up[child].to_string(),
+        ]);
+        let next_indent = format!("{}{}  ", indent, if last { " " } else { "│" });
+        add_tag_tree_rows(table, rolled_up, child, &next_indent);
+    }
+}
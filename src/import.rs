@@ -0,0 +1,569 @@
+//! Contains `jrnl import`, which brings in the entries from other journaling tools:
+//! - `jrnl`: the plain text format of the Python jrnl(`jrnl --export txt`), with `@tags`
+//! - `dayone`: the JSON export of Day One
+//! - `daily`: a folder of daily notes, one file per day, like Obsidian's(`YYYY-MM-DD.md`)
+//!   or Logseq's `journals/`(`YYYY_MM_DD.md`), with `#tags`
+//! - `csv`: a CSV file with a column for the date, the text and the tags
+//!
+//! The entries are merged into the journal files in date order. If there is already an entry
+//! for a date, the new records are added to it(leaving out the ones already there), instead
+//! of making a second `# YYYY-MM-DD` heading. So importing the same thing twice changes nothing.
+use crate::backup::backup_before_rewrite;
+use crate::dates::journal_datetime;
+use crate::funcs::*;
+use crate::get_default_path;
+use crate::layout::journal_file;
+use crate::lock::*;
+use crate::utils::*;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use colored::Colorize;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::ErrorKind,
+    path::Path,
+    process,
+};
+
+/// An entry read from another format.
+#[derive(Debug, Clone)]
+pub struct ImportedEntry {
+    /// Date of the entry
+    pub date: NaiveDate,
+
+    /// Time the entry was written, if known
+    pub time: Option<NaiveTime>,
+
+    /// The records(`- text`) of the entry
+    pub records: Vec<String>,
+}
+
+/// The names of the columns to be read from a CSV file.
+#[derive(Debug, Clone)]
+pub struct CsvColumns {
+    /// Column with the date(YYYY-MM-DD, optionally with the time)
+    pub date: String,
+
+    /// Column with the text of the entry
+    pub text: String,
+
+    /// Column with the tags, separated by spaces, commas or semicolons.
+    /// Optional in the file.
+    pub tags: String,
+}
+
+/// The JSON export of Day One; only the fields which are imported.
+#[derive(Deserialize, Debug)]
+struct DayOneExport {
+    entries: Vec<DayOneEntry>,
+}
+
+/// An entry in the JSON export of Day One.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DayOneEntry {
+    /// In UTC, like "2023-05-01T09:15:00Z"
+    creation_date: String,
+
+    #[serde(default)]
+    text: String,
+
+    #[serde(default)]
+    tags: Vec<String>,
+
+    /// Like "Europe/Berlin"
+    #[serde(default)]
+    time_zone: String,
+}
+
+/// Turns a line of text into a record(`- text`), or `None` if it is empty.
+///
+/// Headings(`## Morning`) become records too, so that they are not taken for the
+/// heading of an entry, and bullets(`- `, `* `, `+ `) are not repeated.
+fn to_record(line: &str) -> Option<String> {
+    let mut text = line.trim();
+    if text.starts_with('#') && text.trim_start_matches('#').starts_with(' ') {
+        text = text.trim_start_matches('#').trim();
+    }
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = text.strip_prefix(bullet) {
+            text = rest.trim();
+            break;
+        }
+    }
+    if text.is_empty() {
+        return None;
+    }
+    Some(format!("- {}", text))
+}
+
+/// Replaces the tags written with a symbol(`@work`, `#work/projx`) with `[work]`.
+/// Only the words starting with the symbol are tags, so e-mails and links are left alone.
+///
+/// ## Example:
+/// ```
+/// assert_eq!(convert_tags("Met @alice at @work.", '@'), "Met [alice] at [work].");
+/// assert_eq!(convert_tags("## Notes #work/projx", '#'), "## Notes [work/projx]");
+/// ```
+fn convert_tags(text: &str, symbol: char) -> String {
+    let mut words: Vec<String> = Vec::new();
+    for word in text.split(' ') {
+        let Some(rest) = word.strip_prefix(symbol) else {
+            words.push(word.to_string());
+            continue;
+        };
+        let tag_len = rest
+            .find(|c: char| !(c.is_alphanumeric() || ['_', '-', '/'].contains(&c)))
+            .unwrap_or(rest.len());
+        if tag_len == 0 {
+            words.push(word.to_string());
+            continue;
+        }
+        words.push(format!("[{}]{}", &rest[..tag_len], &rest[tag_len..]));
+    }
+    words.join(" ")
+}
+
+/// Adds the tags at the end of the first record.
+fn add_tags(records: &mut Vec<String>, tags: &[String]) {
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().trim_start_matches(['#', '@']))
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| format!("[{}]", tag))
+        .collect();
+    if tags.is_empty() {
+        return;
+    }
+    match records.first_mut() {
+        Some(first) => first.push_str(&format!(" {}", tags.join(" "))),
+        None => records.push(format!("- {}", tags.join(" "))),
+    }
+}
+
+/// Returns the date, time and title, if the line is the heading of an entry in the Python
+/// jrnl's format: `[2023-05-01 09:15] Title` or `[2023-05-01 09:15 AM] Title`.
+fn parse_jrnl_heading(line: &str) -> Option<(NaiveDate, Option<NaiveTime>, &str)> {
+    let (stamp, title) = line.strip_prefix('[')?.split_once(']')?;
+    let stamp = stamp.trim();
+    for format in [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %I:%M %p",
+        "%Y-%m-%d %I:%M:%S %p",
+    ] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(stamp, format) {
+            return Some((datetime.date(), Some(datetime.time()), title));
+        }
+    }
+    let date = NaiveDate::parse_from_str(stamp, "%Y-%m-%d").ok()?;
+    Some((date, None, title))
+}
+
+/// Returns the entries in the plain text format of the Python jrnl:
+/// ```text
+/// [2023-05-01 09:15] Went hiking with @family.
+/// It rained on the way back.
+///
+/// [2023-05-02 18:00] Started reading Dune @reading
+/// ```
+fn parse_jrnl(contents: &str) -> Vec<ImportedEntry> {
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+    for line in contents.lines() {
+        if let Some((date, time, title)) = parse_jrnl_heading(line) {
+            entries.push(ImportedEntry {
+                date,
+                time,
+                records: to_record(&convert_tags(title, '@')).into_iter().collect(),
+            });
+            continue;
+        }
+        if let Some(entry) = entries.last_mut() {
+            entry.records.extend(to_record(&convert_tags(line, '@')));
+        }
+    }
+    entries
+}
+
+/// Returns the entries in the JSON export of Day One.
+/// The time is taken in the entry's timezone, or in the configured `timezone`.
+fn parse_dayone(contents: &str) -> Result<Vec<ImportedEntry>, String> {
+    let export: DayOneExport = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+    for entry in export.entries {
+        let created = DateTime::parse_from_rfc3339(&entry.creation_date)
+            .map_err(|e| format!("{}: {}", entry.creation_date, e))?
+            .with_timezone(&Utc);
+        let created = match entry.time_zone.parse::<Tz>() {
+            Ok(tz) => created.with_timezone(&tz).naive_local(),
+            Err(_) => journal_datetime(created),
+        };
+        // Day One escapes the Markdown characters, like `\.`
+        let mut text = String::new();
+        let mut chars = entry.text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\\' && chars.peek().is_some_and(|next| next.is_ascii_punctuation()) {
+                continue;
+            }
+            text.push(c);
+        }
+        let mut records: Vec<String> = text.lines().filter_map(to_record).collect();
+        add_tags(&mut records, &entry.tags);
+        entries.push(ImportedEntry {
+            date: created.date(),
+            time: Some(created.time()),
+            records,
+        });
+    }
+    Ok(entries)
+}
+
+/// Returns the entries in a folder of daily notes, named `YYYY-MM-DD.md` or `YYYY_MM_DD.md`
+/// (in any sub-folder). The front matter(between `---` lines at the top) is left out.
+fn parse_daily(folder: &str) -> Vec<ImportedEntry> {
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+    let mut dirs: Vec<String> = vec![folder.to_string()];
+    while let Some(dir) = dirs.pop() {
+        let paths = match fs::read_dir(&dir) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+        for path in paths.flatten() {
+            let name = path.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            if path.path().is_dir() {
+                dirs.push(path.path().display().to_string());
+                continue;
+            }
+            let Some(stem) = name.strip_suffix(".md") else {
+                continue;
+            };
+            let Ok(date) = NaiveDate::parse_from_str(&stem.replace('_', "-"), "%Y-%m-%d") else {
+                continue;
+            };
+            let contents = fs::read_to_string(path.path()).unwrap_or_default();
+            let mut lines: Vec<&str> = contents.lines().collect();
+            if lines.first().is_some_and(|line| line.trim() == "---")
+                && let Some(end) = lines[1..].iter().position(|line| line.trim() == "---")
+            {
+                lines.drain(..end + 2);
+            }
+            entries.push(ImportedEntry {
+                date,
+                time: None,
+                records: lines
+                    .iter()
+                    .filter_map(|line| to_record(&convert_tags(line, '#')))
+                    .collect(),
+            });
+        }
+    }
+    entries
+}
+
+/// Returns the rows of a CSV file. Quoted fields can have commas, quotes(`""`) and new lines.
+fn parse_csv_rows(contents: &str) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => (),
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+/// Returns the date and time(if any) in a date column, like "2023-05-01",
+/// "2023-05-01 09:15" or "2023-05-01T09:15:00Z".
+fn parse_csv_date(value: &str) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        let datetime = journal_datetime(datetime.with_timezone(&Utc));
+        return Some((datetime.date(), Some(datetime.time())));
+    }
+    for format in [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Some((datetime.date(), Some(datetime.time())));
+        }
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some((date, None))
+}
+
+/// Returns the entries in a CSV file, one entry per row, with the columns named in the header.
+fn parse_csv(contents: &str, columns: &CsvColumns) -> Result<Vec<ImportedEntry>, String> {
+    let rows = parse_csv_rows(contents);
+    let Some((header, rows)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(name))
+    };
+    let date_column =
+        column(&columns.date).ok_or(format!("There is no column named '{}'", columns.date))?;
+    let text_column =
+        column(&columns.text).ok_or(format!("There is no column named '{}'", columns.text))?;
+    let tags_column = column(&columns.tags);
+
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let field = |column: usize| row.get(column).map(|f| f.as_str()).unwrap_or_default();
+        let Some((date, time)) = parse_csv_date(field(date_column)) else {
+            eprintln!(
+                "{}: row {} has no valid date('{}'), skipping it.",
+                "WARNING".yellow().bold(),
+                i + 2,
+                field(date_column)
+            );
+            continue;
+        };
+        let mut records: Vec<String> = field(text_column).lines().filter_map(to_record).collect();
+        if let Some(tags_column) = tags_column {
+            let tags: Vec<String> = field(tags_column)
+                .split([',', ';', ' '])
+                .map(|tag| tag.trim_matches(['[', ']']).to_string())
+                .collect();
+            add_tags(&mut records, &tags);
+        }
+        entries.push(ImportedEntry {
+            date,
+            time,
+            records,
+        });
+    }
+    Ok(entries)
+}
+
+/// Merges the entries(sorted by date) into the contents of a journal file, keeping the
+/// entries in date order.
+///
+/// Returns the new contents, the number of entries added, and the number of records
+/// added to the entries which were already there.
+fn merge_into_file(contents: &str, entries: &[ImportedEntry]) -> (String, usize, usize) {
+    let mut lines: Vec<String> = contents.split('\n').map(String::from).collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let mut entries_added = 0;
+    let mut records_added = 0;
+
+    for entry in entries {
+        let date = entry.date.format("%Y-%m-%d").to_string();
+        // (line number, date)
+        let headings: Vec<(usize, String)> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with("# "))
+            .map(|(i, line)| (i, line[1..].trim().to_string()))
+            .collect();
+
+        if let Some(pos) = headings.iter().position(|(_, heading)| *heading == date) {
+            // Add the records which are not there yet, at the end of the entry
+            let start = headings[pos].0;
+            let mut end = match headings.get(pos + 1) {
+                Some((next, _)) => *next,
+                None => lines.len(),
+            };
+            while end > start + 1
+                && (lines[end - 1].starts_with("### ") || lines[end - 1].trim().is_empty())
+            {
+                end -= 1;
+            }
+            let existing: HashSet<String> = lines[start..end]
+                .iter()
+                .map(|line| line.trim().to_string())
+                .collect();
+            let mut new_records: Vec<String> = Vec::new();
+            for record in entry.records.iter() {
+                if !existing.contains(record.trim()) && !new_records.contains(record) {
+                    new_records.push(record.clone());
+                }
+            }
+            records_added += new_records.len();
+            lines.splice(end..end, new_records);
+            continue;
+        }
+
+        let mut block: Vec<String> = entry_heading(entry.date, entry.time)
+            .split('\n')
+            .map(String::from)
+            .collect();
+        block.extend(entry.records.iter().cloned());
+        entries_added += 1;
+        match headings.iter().find(|(_, heading)| *heading > date) {
+            Some((next, _)) => {
+                // Before the weekday and time of the next entry
+                let mut at = *next;
+                while at > 0 && lines[at - 1].starts_with("### ") {
+                    at -= 1;
+                }
+                block.push(String::new());
+                lines.splice(at..at, block);
+            }
+            None => {
+                lines.push(String::new());
+                lines.extend(block);
+            }
+        }
+    }
+    lines.push(String::new());
+    (lines.join("\n"), entries_added, records_added)
+}
+
+/// Imports the entries from the file(or folder, for `daily`) in the given format, after
+/// showing what will be changed and asking for confirmation.
+pub fn import_entries(format: &str, path: &str, columns: &CsvColumns) {
+    let path = shellexpand::tilde(path).into_owned();
+    if !Path::new(&path).exists() {
+        eprintln!("{}", format!("{} does not exist.", path).red());
+        process::exit(1);
+    }
+    let entries = if format == "daily" {
+        parse_daily(&path)
+    } else {
+        let contents = match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) => panic!("Error reading file: {}", e),
+        };
+        let result = match format {
+            "jrnl" => Ok(parse_jrnl(&contents)),
+            "dayone" => parse_dayone(&contents),
+            _ => parse_csv(&contents, columns),
+        };
+        match result {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("{}", format!("Could not read {}: {}", path, e).red());
+                process::exit(1);
+            }
+        }
+    };
+    if entries.is_empty() {
+        eprintln!("{}", format!("No entries found in {}", path).red());
+        process::exit(1);
+    }
+    let jrnl_folder = format!("{}/jrnl_folder", get_default_path());
+    if !Path::new(&jrnl_folder).is_dir() {
+        eprintln!(
+            "{}",
+            "There doesn't seem to be a `jrnl_folder`. Please create it.".red()
+        );
+        process::exit(1);
+    }
+
+    // Several entries on the same day become one
+    let mut by_date: BTreeMap<NaiveDate, ImportedEntry> = BTreeMap::new();
+    for entry in entries {
+        match by_date.get_mut(&entry.date) {
+            Some(existing) => {
+                existing.records.extend(entry.records);
+                existing.time = match (existing.time, entry.time) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            None => {
+                by_date.insert(entry.date, entry);
+            }
+        }
+    }
+    // Sorted by the date of the first entry in each file
+    let mut by_file: Vec<(String, Vec<ImportedEntry>)> = Vec::new();
+    for (date, entry) in by_date {
+        let filename = journal_file(date);
+        match by_file.iter_mut().find(|(name, _)| *name == filename) {
+            Some((_, entries)) => entries.push(entry),
+            None => by_file.push((filename, vec![entry])),
+        }
+    }
+
+    // (filename, contents), to check that they are unchanged when writing
+    let mut read_files: Vec<(String, String)> = Vec::new();
+    // (filename, new contents)
+    let mut changed_files: Vec<(String, String)> = Vec::new();
+    let mut total_entries = 0;
+    let mut total_records = 0;
+    for (filename, entries) in by_file {
+        let contents = match fs::read_to_string(&filename) {
+            Ok(data) => data,
+            Err(e) => match e.kind() {
+                ErrorKind::NotFound => String::new(),
+                other => panic!("Error reading file: {other}"),
+            },
+        };
+        let (new_contents, entries_added, records_added) = merge_into_file(&contents, &entries);
+        read_files.push((filename.clone(), contents));
+        if entries_added + records_added == 0 {
+            continue;
+        }
+        println!(
+            "{}: {} new entries, {} records added to existing entries",
+            filename.underline(),
+            entries_added.to_string().green(),
+            records_added.to_string().yellow()
+        );
+        total_entries += entries_added;
+        total_records += records_added;
+        changed_files.push((filename, new_contents));
+    }
+
+    if changed_files.is_empty() {
+        println!("{}", "Everything is already in the journal.".yellow());
+        return;
+    }
+    println!(
+        "\n{} new entries, and {} records added to existing entries, in {} file(s).",
+        total_entries.to_string().bold(),
+        total_records.to_string().bold(),
+        changed_files.len().to_string().bold()
+    );
+    if !inquire_confirm("Import these entries?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Held until everything is written, so that no change made meanwhile is lost
+    let _lock = lock_unchanged(&read_files);
+    backup_before_rewrite();
+    for (filename, contents) in changed_files {
+        if let Some(folder) = Path::new(&filename).parent()
+            && let Err(e) = fs::create_dir_all(folder)
+        {
+            panic!("An error occured: {}", e);
+        }
+        match write_journal(&filename, &contents) {
+            Ok(_) => (),
+            Err(e) => panic!("An error occured: {}", e),
+        }
+    }
+    println!("{}", "Done.".green());
+}
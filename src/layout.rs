@@ -0,0 +1,641 @@
+//! Contains the layout of the journal files in `jrnl_folder`, set by `layout` in the config:
+//! - "monthly"(the default): a file per month, `2026/2026_10.md`
+//! - "daily": a file per day, `2026/10/2026-10-18.md`
+//! - "yearly": a file per year, `2026/2026.md`
+//! - a custom pattern with `{YYYY}`, `{MM}` and `{DD}`, like "entries/{YYYY}-{MM}.md"
+//!
+//! Every path to a journal file is found through here, and `jrnl migrate` moves the entries
+//! from one layout to another.
+use crate::backup::backup_before_rewrite;
+use crate::funcs::*;
+use crate::get_default_path;
+use crate::lock::*;
+use chrono::{Datelike, Months, NaiveDate};
+use colored::Colorize;
+use std::{collections::BTreeMap, fs, path::Path, process};
+
+/// The pattern of the "monthly" layout.
+pub const MONTHLY: &str = "{YYYY}/{YYYY}_{MM}.md";
+
+/// The pattern of the "daily" layout.
+pub const DAILY: &str = "{YYYY}/{MM}/{YYYY}-{MM}-{DD}.md";
+
+/// The pattern of the "yearly" layout.
+pub const YEARLY: &str = "{YYYY}/{YYYY}.md";
+
+/// Returns the pattern of a layout, given its name("monthly", "daily", "yearly") or a custom
+/// pattern. An empty name is the "monthly" layout.
+pub fn layout_pattern(layout: &str) -> String {
+    match layout.trim().to_lowercase().as_str() {
+        "" | "monthly" => MONTHLY.to_string(),
+        "daily" => DAILY.to_string(),
+        "yearly" => YEARLY.to_string(),
+        _ => layout.trim().to_string(),
+    }
+}
+
+/// Checks if a pattern can be used: it must have the year, the month if it has the day,
+/// and be a Markdown file inside `jrnl_folder`.
+pub fn is_valid_pattern(pattern: &str) -> bool {
+    pattern.contains("{YYYY}")
+        && (!pattern.contains("{DD}") || pattern.contains("{MM}"))
+        && pattern.ends_with(".md")
+        && !pattern.starts_with('/')
+        && !pattern.split('/').any(|part| part == "..")
+}
+
+/// Returns the pattern of the configured `layout`. Exits if it is not a valid one, as the
+/// journal files could not be found.
+pub fn config_layout() -> String {
+    let layout = read_config().0.layout;
+    let pattern = layout_pattern(&layout);
+    if !is_valid_pattern(&pattern) {
+        exit_invalid_layout(&format!("'{}' is not a valid `layout`.", layout));
+    }
+    pattern
+}
+
+/// Prints the error about an invalid layout, with the ones which can be used, and exits.
+fn exit_invalid_layout(error: &str) -> ! {
+    eprintln!("{}", error.red());
+    eprintln!(
+        "{}: Use monthly, daily, yearly, or a pattern with {{YYYY}}, {{MM}} and {{DD}}, like \"entries/{{YYYY}}-{{MM}}.md\".",
+        "HELP".green().bold()
+    );
+    process::exit(1);
+}
+
+/// Returns the path, relative to `jrnl_folder`, of the file containing the date's entry.
+///
+/// ## Example:
+/// ```
+/// let date = NaiveDate::from_ymd_opt(2026, 10, 8).unwrap();
+/// assert_eq!(journal_path(MONTHLY, date), "2026/2026_10.md");
+/// assert_eq!(journal_path(DAILY, date), "2026/10/2026-10-08.md");
+/// ```
+pub fn journal_path(pattern: &str, date: NaiveDate) -> String {
+    pattern
+        .replace("{YYYY}", &format!("{:04}", date.year()))
+        .replace("{MM}", &format!("{:02}", date.month()))
+        .replace("{DD}", &format!("{:02}", date.day()))
+}
+
+/// Returns the path of the file containing the date's entry, as per the configured `layout`.
+pub fn journal_file(date: NaiveDate) -> String {
+    format!(
+        "{}/jrnl_folder/{}",
+        get_default_path(),
+        journal_path(&config_layout(), date)
+    )
+}
+
+/// Returns the path of the file containing the date's entry, after making its folders
+/// (like `2026/10/` in the "daily" layout), if `jrnl_folder` is there.
+pub fn journal_file_for_writing(date: NaiveDate) -> String {
+    let filename = journal_file(date);
+    let jrnl_folder = format!("{}/jrnl_folder", get_default_path());
+    if let Some(parent) = Path::new(&filename).parent()
+        && Path::new(&jrnl_folder).is_dir()
+        && let Err(e) = fs::create_dir_all(parent)
+    {
+        panic!("An error occured: {}", e);
+    }
+    filename
+}
+
+/// Returns the first and last day covered by a file, if its path(relative to `jrnl_folder`)
+/// follows the pattern.
+///
+/// ## Example:
+/// ```
+/// let (first, last) = match_pattern(MONTHLY, "2026/2026_02.md").unwrap();
+/// assert_eq!(first, NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
+/// assert_eq!(last, NaiveDate::from_ymd_opt(2026, 2, 28).unwrap());
+/// assert_eq!(match_pattern(MONTHLY, "2026/notes.md"), None);
+/// ```
+pub fn match_pattern(pattern: &str, path: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let mut values: [Option<u32>; 3] = [None; 3];
+    let mut pattern = pattern;
+    let mut path = path;
+    while !pattern.is_empty() {
+        let placeholder = [("{YYYY}", 4), ("{MM}", 2), ("{DD}", 2)]
+            .iter()
+            .enumerate()
+            .find(|(_, (name, _))| pattern.starts_with(name));
+        match placeholder {
+            Some((i, (name, digits))) => {
+                let value = path.get(..*digits)?;
+                if !value.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                let value: u32 = value.parse().ok()?;
+                // The same placeholder must have the same value everywhere
+                if values[i].is_some_and(|v| v != value) {
+                    return None;
+                }
+                values[i] = Some(value);
+                pattern = &pattern[name.len()..];
+                path = &path[*digits..];
+            }
+            None => {
+                let c = pattern.chars().next()?;
+                path = path.strip_prefix(c)?;
+                pattern = &pattern[c.len_utf8()..];
+            }
+        }
+    }
+    if !path.is_empty() {
+        return None;
+    }
+    let year = values[0]? as i32;
+    match (values[1], values[2]) {
+        (Some(month), Some(day)) => {
+            let date = NaiveDate::from_ymd_opt(year, month, day)?;
+            Some((date, date))
+        }
+        (Some(month), None) => {
+            let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+            Some((first, first + Months::new(1) - chrono::Duration::days(1)))
+        }
+        _ => Some((
+            NaiveDate::from_ymd_opt(year, 1, 1)?,
+            NaiveDate::from_ymd_opt(year, 12, 31)?,
+        )),
+    }
+}
+
+/// Checks if a file(relative to `jrnl_folder`) is a journal file in the configured `layout`.
+pub fn is_journal_file(path: &str) -> bool {
+    match_pattern(&config_layout(), path).is_some()
+}
+
+/// Returns all the files in `jrnl_folder` following the pattern, as
+/// (first day covered, last day covered, filename), sorted by date.
+pub fn get_journal_files_with(pattern: &str) -> Vec<(NaiveDate, NaiveDate, String)> {
+    get_journal_files_in(&format!("{}/jrnl_folder", get_default_path()), pattern)
+}
+
+/// Returns all the files in the folder(a `jrnl_folder`) following the pattern, as
+/// (first day covered, last day covered, filename), sorted by date.
+pub fn get_journal_files_in(root: &str, pattern: &str) -> Vec<(NaiveDate, NaiveDate, String)> {
+    let mut files: Vec<(NaiveDate, NaiveDate, String)> = Vec::new();
+    let mut dirs: Vec<String> = vec![String::new()];
+    while let Some(dir) = dirs.pop() {
+        let paths = match fs::read_dir(format!("{}/{}", root, dir)) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+        for path in paths.flatten() {
+            let name = path.file_name().to_string_lossy().to_string();
+            let rel_name = if dir.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", dir, name)
+            };
+            if name.starts_with('.') || (dir.is_empty() && name == "attachments") {
+                continue;
+            }
+            if path.path().is_dir() {
+                dirs.push(rel_name);
+            } else if let Some((first, last)) = match_pattern(pattern, &rel_name) {
+                files.push((first, last, format!("{}/{}", root, rel_name)));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Returns all the journal files in the configured `layout`, as
+/// (first day covered, last day covered, filename), sorted by date.
+pub fn get_journal_files() -> Vec<(NaiveDate, NaiveDate, String)> {
+    get_journal_files_with(&config_layout())
+}
+
+/// Returns the journal files with some days between `start` and `end`.
+pub fn get_journal_files_between(start: NaiveDate, end: NaiveDate) -> Vec<String> {
+    get_journal_files()
+        .into_iter()
+        .filter(|(first, last, _)| *first <= end && *last >= start)
+        .map(|(_, _, filename)| filename)
+        .collect()
+}
+
+/// Returns where the entries of a journal file(its lines) are, as (first line, line of the
+/// heading, line after the end). The weekday and time(`### ...`) above a heading belong to
+/// its entry.
+pub fn entry_ranges(lines: &[&str]) -> Vec<(usize, usize, usize)> {
+    // (line where the entry starts, line of its heading)
+    let mut starts: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("# ") {
+            let previous_heading = starts.last().map(|(_, h)| h + 1).unwrap_or(0);
+            let mut start = i;
+            while start > previous_heading && lines[start - 1].starts_with("### ") {
+                start -= 1;
+            }
+            starts.push((start, i));
+        }
+    }
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, (start, heading))| {
+            let end = match starts.get(n + 1) {
+                Some((next, _)) => *next,
+                None => lines.len(),
+            };
+            (*start, *heading, end)
+        })
+        .collect()
+}
+
+/// Splits the contents of a journal file into its entries, as (date, lines of the entry).
+/// The weekday and time(`### ...`) above a heading belong to its entry, and anything before
+/// the first entry goes with it.
+pub fn split_entries(contents: &str) -> Vec<(String, Vec<String>)> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+    for (n, (start, heading, end)) in entry_ranges(&lines).into_iter().enumerate() {
+        let start = if n == 0 { 0 } else { start };
+        let mut entry: Vec<String> = lines[start..end].iter().map(|l| l.to_string()).collect();
+        while entry.first().is_some_and(|l| l.trim().is_empty()) {
+            entry.remove(0);
+        }
+        while entry.last().is_some_and(|l| l.trim().is_empty()) {
+            entry.pop();
+        }
+        entries.push((lines[heading][1..].trim().to_string(), entry));
+    }
+    entries
+}
+
+/// Changes the links to attachments(`](../attachments/...)`) in the lines of an entry
+/// moved from one journal file to another, as they are relative to the file.
+pub fn relink_attachments(lines: &[String], old_path: &str, new_path: &str) -> Vec<String> {
+    let old_link = format!(
+        "]({}attachments/",
+        "../".repeat(old_path.matches('/').count())
+    );
+    let new_link = format!(
+        "]({}attachments/",
+        "../".repeat(new_path.matches('/').count())
+    );
+    lines
+        .iter()
+        .map(|line| line.replace(&old_link, &new_link))
+        .collect()
+}
+
+/// The entries of a journal, by date. A date can have more than one entry, in the order
+/// they are in the files.
+pub type Entries = BTreeMap<NaiveDate, Vec<Vec<String>>>;
+
+/// Changes the links to attachments in the entries, from where they are in the files of one
+/// layout(its pattern) to where they are in those of another.
+pub fn relink_entries(entries: &Entries, from: &str, to: &str) -> Entries {
+    entries
+        .iter()
+        .map(|(date, date_entries)| {
+            let (old_path, new_path) = (journal_path(from, *date), journal_path(to, *date));
+            let date_entries = date_entries
+                .iter()
+                .map(|lines| relink_attachments(lines, &old_path, &new_path))
+                .collect();
+            (*date, date_entries)
+        })
+        .collect()
+}
+
+/// Returns the pattern of the layout of the journal in the folder(a `jrnl_folder`): the one
+/// kept in its `.sync/layout` by `jrnl sync`, or else the configured one.
+pub fn folder_layout(root: &str) -> String {
+    match fs::read_to_string(format!("{}/.sync/layout", root)) {
+        Ok(layout) if is_valid_pattern(layout.trim()) => layout.trim().to_string(),
+        _ => config_layout(),
+    }
+}
+
+/// Keeps the pattern of the layout in the folder's(a `jrnl_folder`) `.sync/layout`, for
+/// the `jrnl`s syncing with it to read it in that layout.
+pub fn write_folder_layout(root: &str, pattern: &str) {
+    if let Err(e) = fs::create_dir_all(format!("{}/.sync", root)) {
+        panic!("An error occured: {}", e);
+    }
+    if let Err(e) = write_atomic(&format!("{}/.sync/layout", root), format!("{}\n", pattern)) {
+        panic!("An error occured: {}", e);
+    }
+}
+
+/// Reads every entry of the journal in the folder(a `jrnl_folder`), in the layout of the
+/// pattern. Exits if a heading is not a date, as the file could not be written back.
+pub fn read_entries_in(root: &str, pattern: &str) -> Entries {
+    let mut entries: Entries = BTreeMap::new();
+    for (_, _, filename) in get_journal_files_in(root, pattern) {
+        let contents = match fs::read_to_string(&filename) {
+            Ok(data) => data,
+            Err(e) => panic!("Error reading file: {}", e),
+        };
+        for (date, lines) in split_entries(&contents) {
+            let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "The heading `# {}` in {} is not a date. Fix it first.",
+                        date, filename
+                    )
+                    .red()
+                );
+                process::exit(1);
+            };
+            entries.entry(date).or_default().push(lines);
+        }
+    }
+    entries
+}
+
+/// Returns the contents of the journal files(in the layout of the pattern) holding the
+/// dates, from all the entries, as (file relative to `jrnl_folder`, contents). A file left
+/// without entries is empty.
+pub fn entries_contents(
+    entries: &Entries,
+    pattern: &str,
+    dates: &[NaiveDate],
+) -> BTreeMap<String, String> {
+    let mut files: BTreeMap<String, String> = dates
+        .iter()
+        .map(|date| (journal_path(pattern, *date), String::new()))
+        .collect();
+    for (date, date_entries) in entries.iter() {
+        let Some(contents) = files.get_mut(&journal_path(pattern, *date)) else {
+            continue;
+        };
+        for lines in date_entries {
+            contents.push_str(&format!("\n{}\n", lines.join("\n")));
+        }
+    }
+    files
+}
+
+/// Writes the journal files(in the folder, a `jrnl_folder`, in the layout of the pattern)
+/// holding the dates, from all the entries. Files left without entries are removed.
+pub fn write_entries_in(root: &str, pattern: &str, entries: &Entries, dates: &[NaiveDate]) {
+    for (file, contents) in entries_contents(entries, pattern, dates) {
+        write_journal_file_in(root, &file, &contents);
+    }
+}
+
+/// Writes a journal file(relative to the folder, a `jrnl_folder`), making its folders.
+/// If there is nothing left in it, it is removed, along with the folders left empty.
+pub fn write_journal_file_in(root: &str, file: &str, contents: &str) {
+    let filename = format!("{}/{}", root, file);
+    if contents.trim().is_empty() {
+        if Path::new(&filename).exists()
+            && let Err(e) = fs::remove_file(&filename)
+        {
+            panic!("An error occured: {}", e);
+        }
+        // Remove the folders left empty, like `2024/`
+        let mut dir = Path::new(&filename).parent();
+        while let Some(d) = dir {
+            if d == Path::new(root) || fs::remove_dir(d).is_err() {
+                break;
+            }
+            dir = d.parent();
+        }
+        return;
+    }
+    if let Some(parent) = Path::new(&filename).parent()
+        && let Err(e) = fs::create_dir_all(parent)
+    {
+        panic!("An error occured: {}", e);
+    }
+    if let Err(e) = write_journal(&filename, contents) {
+        panic!("An error occured: {}", e);
+    }
+}
+
+/// Returns the `jrnl_folder` meant by the path(like a `--path`): the folder itself, or the
+/// one inside it. Exits if it does not seem to be one.
+pub fn resolve_jrnl_folder(path: &str) -> String {
+    let path = shellexpand::tilde(path).into_owned();
+    let inner = format!("{}/jrnl_folder", path.trim_end_matches('/'));
+    let root = if Path::new(&inner).is_dir() {
+        inner
+    } else {
+        path
+    };
+    if !Path::new(&root).is_dir() {
+        eprintln!("{}", format!("{} is not a folder.", root).red());
+        process::exit(1);
+    }
+    // An empty folder is fine, to start a copy of the journal in it
+    let pattern = folder_layout(&root);
+    if !is_empty_folder(&root) && get_journal_files_in(&root, &pattern).is_empty() {
+        eprintln!(
+            "{}",
+            format!(
+                "{} does not seem to be a `jrnl_folder`: it has no journal files in the layout {}.",
+                root, pattern
+            )
+            .red()
+        );
+        eprintln!(
+            "{}: If it is in another layout, put its pattern in {}/.sync/layout.",
+            "HELP".green().bold(),
+            root
+        );
+        process::exit(1);
+    }
+    root
+}
+
+/// Checks if the folder has nothing in it but the hidden files of `jrnl`(like `.lock`).
+pub fn is_empty_folder(root: &str) -> bool {
+    fs::read_dir(root).is_ok_and(|paths| {
+        paths
+            .flatten()
+            .all(|path| path.file_name().to_string_lossy().starts_with('.'))
+    })
+}
+
+/// Moves every entry into the files of another layout(a name or a pattern), after showing
+/// what will be done and asking for confirmation. The `layout` in the config file is
+/// then changed to the new one.
+pub fn migrate(layout: &str) {
+    let from = config_layout();
+    let to = layout_pattern(layout);
+    if !is_valid_pattern(&to) {
+        exit_invalid_layout(&format!("'{}' is not a valid layout.", layout));
+    }
+    if from == to {
+        println!("The journal is already in the layout {}", to.cyan());
+        return;
+    }
+    let root = format!("{}/jrnl_folder", get_default_path());
+    let old_files = get_journal_files_with(&from);
+    if old_files.is_empty() {
+        eprintln!("{}", "There are no entries to migrate.".red());
+        process::exit(1);
+    }
+
+    // New file -> entries in it, in order
+    let mut new_files: BTreeMap<String, Vec<(String, Vec<String>)>> = BTreeMap::new();
+    let mut no_of_entries = 0;
+    // (filename, contents), to check that they are unchanged when writing
+    let mut read_files: Vec<(String, String)> = Vec::new();
+    for (_, _, filename) in old_files.iter() {
+        let contents = match fs::read_to_string(filename) {
+            Ok(data) => data,
+            Err(e) => panic!("Error reading file: {}", e),
+        };
+        read_files.push((filename.clone(), contents.clone()));
+        let entries = split_entries(&contents);
+        if entries.is_empty() && !contents.trim().is_empty() {
+            eprintln!(
+                "{}",
+                format!(
+                    "{} has no entries(`# YYYY-MM-DD`). Move it before migrating.",
+                    filename
+                )
+                .red()
+            );
+            process::exit(1);
+        }
+        for (date, lines) in entries {
+            let Ok(date_naive) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "The heading `# {}` in {} is not a date. Fix it before migrating.",
+                        date, filename
+                    )
+                    .red()
+                );
+                process::exit(1);
+            };
+            let new_path = journal_path(&to, date_naive);
+            let lines = relink_attachments(&lines, &journal_path(&from, date_naive), &new_path);
+            let new_file = format!("{}/{}", root, new_path);
+            new_files.entry(new_file).or_default().push((date, lines));
+            no_of_entries += 1;
+        }
+    }
+    let old_names: Vec<&String> = old_files.iter().map(|(_, _, filename)| filename).collect();
+    for new_file in new_files.keys() {
+        if Path::new(new_file).exists() && !old_names.contains(&new_file) {
+            eprintln!(
+                "{}",
+                format!("{} already exists. Nothing was changed.", new_file).red()
+            );
+            process::exit(1);
+        }
+        if !old_names.contains(&new_file) {
+            // Must still not exist when writing
+            read_files.push((new_file.clone(), String::new()));
+        }
+    }
+
+    println!(
+        "{} entries in {} file(s) will be moved from {} to {}, into {} file(s).",
+        no_of_entries.to_string().bold(),
+        old_files.len().to_string().bold(),
+        from.cyan(),
+        to.cyan(),
+        new_files.len().to_string().bold()
+    );
+    if !inquire_confirm("Migrate the journal?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Held until everything is written, so that no change made meanwhile is lost
+    let _lock = lock_unchanged(&read_files);
+    backup_before_rewrite();
+
+    for (new_file, mut entries) in new_files.iter().map(|(f, e)| (f, e.clone())) {
+        // Stable sort, so that the entries of the same day stay in order
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut contents = String::new();
+        for (_, lines) in entries {
+            contents.push_str(&format!("\n{}\n", lines.join("\n")));
+        }
+        if let Some(parent) = Path::new(new_file).parent()
+            && let Err(e) = fs::create_dir_all(parent)
+        {
+            panic!("An error occured: {}", e);
+        }
+        match write_journal(new_file, &contents) {
+            Ok(_) => (),
+            Err(e) => panic!("An error occured: {}", e),
+        }
+    }
+    for (_, _, filename) in old_files.iter() {
+        if new_files.contains_key(filename) {
+            continue;
+        }
+        if let Err(e) = fs::remove_file(filename) {
+            panic!("An error occured: {}", e);
+        }
+        // Remove the folders left empty, like `2026/10/`
+        let mut dir = Path::new(filename).parent();
+        while let Some(d) = dir {
+            if d == Path::new(&root) || fs::remove_dir(d).is_err() {
+                break;
+            }
+            dir = d.parent();
+        }
+    }
+
+    // So that the journals syncing with this one read it in the new layout
+    if Path::new(&format!("{}/.sync", root)).is_dir() {
+        write_folder_layout(&root, &to);
+    }
+    set_config_layout(layout);
+    println!("{}", "Done.".green());
+}
+
+/// Sets `layout` in the config file, keeping everything else in it as it is.
+fn set_config_layout(layout: &str) {
+    let config_file = shellexpand::tilde("~/.config/jrnl/config.toml").into_owned();
+    let line = format!("layout = \"{}\"", layout.trim());
+    let contents = match fs::read_to_string(&config_file) {
+        Ok(data) => data,
+        Err(_) => {
+            println!(
+                "{}: Add `{}` to your config file, for the journal to be read in the new layout.",
+                "HELP".green().bold(),
+                line
+            );
+            return;
+        }
+    };
+    let mut found = false;
+    let mut lines: Vec<String> = Vec::new();
+    for l in contents.lines() {
+        // `layout` is a top-level key, so it must come before the first table(`[...]`)
+        if l.trim_start().starts_with('[') && !found {
+            // Above the comments of the table
+            let mut at = lines.len();
+            while at > 0 && lines[at - 1].trim_start().starts_with('#') {
+                at -= 1;
+            }
+            lines.insert(at, String::new());
+            lines.insert(at, line.clone());
+            found = true;
+        }
+        let key = l.split('=').next().unwrap_or_default().trim();
+        if key == "layout" && !found {
+            lines.push(line.clone());
+            found = true;
+        } else {
+            lines.push(l.to_string());
+        }
+    }
+    if !found {
+        lines.push(line.clone());
+    }
+    match write_atomic(&config_file, &(lines.join("\n") + "\n")) {
+        Ok(_) => println!("Set `{}` in {}", line, config_file.underline()),
+        Err(e) => panic!("An error occured: {}", e),
+    }
+}
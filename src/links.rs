@@ -0,0 +1,173 @@
+//! Contains the wiki links between entries and notes, and `jrnl backlinks`.
+//!
+//! A link is written in double brackets, in an entry or in a note:
+//! ```text
+//! - [work] continued from [[2026-10-01]], see [[projects/alpha]] and [[events]]
+//! ```
+//! A date links to that day's entry, and anything else to a note in `jrnl_folder`
+//! (the `.md` can be left out).
+use crate::funcs::*;
+use crate::layout::get_journal_files;
+use crate::notes::note_path;
+use crate::utils::*;
+use chrono::NaiveDate;
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use std::{collections::HashSet, fs, process};
+
+/// A wiki link, and where it was found.
+#[derive(Debug, Clone)]
+pub struct Link {
+    /// Where the link is: the date(YYYY-MM-DD) of the entry, or the note's name
+    pub source: String,
+
+    /// Line number(starting at 1) of the link in the file
+    pub line_no: usize,
+
+    /// What is linked to, as written
+    pub target: String,
+
+    /// The whole line containing the link
+    pub line: String,
+}
+
+/// Returns the target in a form that can be compared: notes without the `.md`.
+pub fn normalize_target(target: &str) -> String {
+    target.trim().trim_end_matches(".md").to_string()
+}
+
+/// Returns every link in the journal: in the entries, then in the notes.
+pub fn get_all_links() -> Vec<Link> {
+    collect_links(|line| split_links(line).1)
+}
+
+/// Returns every target found by `extract` in the lines of the entries, then of the notes.
+pub fn collect_links(extract: fn(&str) -> Vec<String>) -> Vec<Link> {
+    let mut links: Vec<Link> = Vec::new();
+    for (_, _, filename) in get_journal_files() {
+        let contents = fs::read_to_string(&filename).unwrap_or_default();
+        let mut entry_date = String::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.starts_with("# ") {
+                entry_date = line[1..].trim().to_string();
+                continue;
+            }
+            for target in extract(line) {
+                links.push(Link {
+                    source: entry_date.clone(),
+                    line_no: i + 1,
+                    target,
+                    line: line.trim().to_string(),
+                });
+            }
+        }
+    }
+    for note in get_note_files() {
+        // Only text files can have links
+        let Ok(contents) = fs::read_to_string(note_path(&note)) else {
+            continue;
+        };
+        for (i, line) in contents.lines().enumerate() {
+            for target in extract(line) {
+                links.push(Link {
+                    source: note.clone(),
+                    line_no: i + 1,
+                    target,
+                    line: line.trim().to_string(),
+                });
+            }
+        }
+    }
+    links
+}
+
+/// Returns the links which point to nothing: a date without an entry, or a note which
+/// does not exist.
+pub fn get_dangling_links() -> Vec<Link> {
+    let entries: HashSet<String> = get_all_entries()
+        .into_iter()
+        .map(|(date, _)| date)
+        .collect();
+    let notes: HashSet<String> = get_note_files()
+        .iter()
+        .map(|note| normalize_target(note))
+        .collect();
+    get_all_links()
+        .into_iter()
+        .filter(|link| {
+            let target = normalize_target(&link.target);
+            if NaiveDate::parse_from_str(&target, "%Y-%m-%d").is_ok() {
+                !entries.contains(&target)
+            } else {
+                !notes.contains(&target)
+            }
+        })
+        .collect()
+}
+
+/// Prints every place which links to the target(a note, or a date).
+///
+/// # Sample output:
+/// Note that the headings are colored in green.
+///
+/// ```text
+/// Links to 2026-10-01:
+/// ╭───────────────────┬──────┬───────────────────────────────╮
+/// │ From              ┆ Line ┆ Record                        │
+/// ╞═══════════════════╪══════╪═══════════════════════════════╡
+/// │ 2026-10-03        ┆ 42   ┆ - continued [[2026-10-01]]    │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ projects/alpha.md ┆ 3    ┆ Kicked off on [[2026-10-01]]  │
+/// ╰───────────────────┴──────┴───────────────────────────────╯
+/// ```
+pub fn print_backlinks(target: &str) {
+    // A note if there is one with the name, else a date(which can be relative, like `yesterday`)
+    let is_note = get_note_files()
+        .iter()
+        .any(|note| normalize_target(note) == normalize_target(target));
+    let target = if is_note {
+        normalize_target(target)
+    } else {
+        match crate::dates::resolve_span(target, crate::dates::journal_today()) {
+            Ok(span) => span.start.format("%Y-%m-%d").to_string(),
+            Err(_) => {
+                eprintln!(
+                    "{}",
+                    format!("There is no such note as {}, and it is not a date.", target).red()
+                );
+                eprintln!(
+                    "{}: Use `jrnl notes` to list the notes.",
+                    "HELP".green().bold()
+                );
+                process::exit(1);
+            }
+        }
+    };
+
+    let links: Vec<Link> = get_all_links()
+        .into_iter()
+        .filter(|link| normalize_target(&link.target) == target)
+        .collect();
+    if links.is_empty() {
+        println!("Nothing links to {}", target.cyan());
+        process::exit(1);
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["From".green(), "Line".green(), "Record".green()]);
+    for link in links.iter() {
+        table.add_row(vec![
+            link.source.cyan().to_string(),
+            link.line_no.to_string(),
+            link.line.clone(),
+        ]);
+    }
+    print_with_pager(
+        &format!("{}\n{}", format!("Links to {}:", target).bold(), table),
+        links.len(),
+    );
+}
@@ -0,0 +1,250 @@
+//! Contains the locking of the journal, so that two `jrnl`s(a second terminal, a script
+//! adding records, `jrnl sync`, etc) never write over each other.
+//!
+//! Every change to the journal files is made while holding an advisory lock on
+//! `jrnl_folder/.lock`, and is written with [`write_atomic`], so a file is never left
+//! half-written.
+//!
+//! The editor can not be made to respect the lock, so while an entry is open in it, the
+//! file is marked in `jrnl_folder/.editing-<pid>`. Other `jrnl`s writing to that file
+//! warn about it, and log the lines they add in the marker. When the editor is closed,
+//! any of those lines which are no longer in the file(the editor saved over them) are
+//! printed in a warning, so they can be added again.
+use crate::funcs::write_atomic;
+use crate::get_default_path;
+use colored::Colorize;
+use std::{
+    fs::{self, File, OpenOptions, TryLockError},
+    io::{ErrorKind, Write},
+    path::Path,
+    process,
+    sync::Mutex,
+};
+
+/// The lock file held by this process, and how many [`JournalLock`]s are alive.
+/// The lock is taken by the first one and released by the last one, so that functions
+/// holding it can call each other.
+static HELD: Mutex<(usize, Option<File>)> = Mutex::new((0, None));
+
+/// Holds the lock on the journal until dropped.
+pub struct JournalLock;
+
+impl Drop for JournalLock {
+    fn drop(&mut self) {
+        let mut held = HELD.lock().unwrap();
+        held.0 -= 1;
+        if held.0 == 0 {
+            // Closing the file releases the lock
+            held.1 = None;
+        }
+    }
+}
+
+/// Takes the lock on the journal, waiting for any other `jrnl` which is writing to it.
+///
+/// If there is no `jrnl_folder`(so there is nothing to protect yet), nothing is locked.
+pub fn lock_journal() -> JournalLock {
+    let mut held = HELD.lock().unwrap();
+    held.0 += 1;
+    if held.0 > 1 {
+        return JournalLock;
+    }
+    let lock_file = format!("{}/jrnl_folder/.lock", get_default_path());
+    let Ok(file) = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_file)
+    else {
+        return JournalLock;
+    };
+    match file.try_lock() {
+        Ok(_) => (),
+        Err(TryLockError::WouldBlock) => {
+            println!(
+                "{}",
+                "Waiting for another jrnl to finish writing...".yellow()
+            );
+            if let Err(e) = file.lock() {
+                panic!("An error occured: {}", e);
+            }
+        }
+        Err(TryLockError::Error(e)) => panic!("An error occured: {}", e),
+    }
+    held.1 = Some(file);
+    JournalLock
+}
+
+/// Takes the lock on the journal after the user has confirmed a preview, and checks that
+/// the files it was made from(as (filename, contents read)) were not changed meanwhile.
+/// Exits if one was, as the preview would no longer match what is written.
+///
+/// The lock is not held while waiting for the user, as that would block every other `jrnl`.
+pub fn lock_unchanged(files: &[(String, String)]) -> JournalLock {
+    let lock = lock_journal();
+    for (filename, contents) in files {
+        let current = match fs::read_to_string(filename) {
+            Ok(data) => data,
+            Err(e) => match e.kind() {
+                ErrorKind::NotFound => String::new(),
+                other => panic!("Error reading file: {other}"),
+            },
+        };
+        if current != *contents {
+            eprintln!(
+                "{}",
+                format!(
+                    "{} was changed by another jrnl meanwhile. Nothing was changed; try again.",
+                    filename
+                )
+                .red()
+            );
+            process::exit(1);
+        }
+    }
+    lock
+}
+
+/// Takes the lock on another journal(its `jrnl_folder`), like for `jrnl sync`, waiting for
+/// any `jrnl` writing to it. The lock is held until the file is dropped.
+pub fn lock_other_journal(root: &str) -> Option<File> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(format!("{}/.lock", root))
+        .ok()?;
+    if let Err(TryLockError::WouldBlock) = file.try_lock() {
+        println!(
+            "{}",
+            format!("Waiting for another jrnl to finish writing to {}...", root).yellow()
+        );
+        if let Err(e) = file.lock() {
+            panic!("An error occured: {}", e);
+        }
+    }
+    Some(file)
+}
+
+/// Writes a journal file atomically, while holding the lock.
+///
+/// If the file is open in the editor by another `jrnl`, a warning is printed, and the
+/// lines added are logged for that `jrnl` to check when the editor is closed.
+pub fn write_journal(filename: &str, contents: &str) -> std::io::Result<()> {
+    let _lock = lock_journal();
+    let old_contents = fs::read_to_string(filename).unwrap_or_default();
+    write_atomic(filename, contents)?;
+
+    let old_lines: Vec<&str> = old_contents.lines().map(str::trim).collect();
+    let added: Vec<&str> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !old_lines.contains(&line.trim()))
+        .collect();
+    for (marker, pid) in editing_markers(filename) {
+        if pid == process::id() {
+            continue;
+        }
+        println!(
+            "{}: {} is open in the editor(by jrnl {}). Reload it there before saving, or this change will be lost.",
+            "WARNING".yellow().bold(),
+            filename.underline(),
+            pid
+        );
+        if added.is_empty() {
+            continue;
+        }
+        if let Ok(mut file) = OpenOptions::new().append(true).open(&marker) {
+            let _ = file.write_all(format!("{}\n", added.join("\n")).as_bytes());
+        }
+    }
+    Ok(())
+}
+
+/// Checks if a process is still running. Where that can not be known(no `/proc`),
+/// it is assumed to be.
+fn is_running(pid: u32) -> bool {
+    !Path::new("/proc/self").exists() || Path::new(&format!("/proc/{}", pid)).exists()
+}
+
+/// Returns the editing markers(path, pid) of the file. Markers left behind by a `jrnl`
+/// which is no longer running are removed.
+fn editing_markers(filename: &str) -> Vec<(String, u32)> {
+    let root = format!("{}/jrnl_folder", get_default_path());
+    let Ok(paths) = fs::read_dir(&root) else {
+        return Vec::new();
+    };
+    let mut markers: Vec<(String, u32)> = Vec::new();
+    for path in paths.flatten() {
+        let name = path.file_name().to_string_lossy().to_string();
+        let Some(pid) = name
+            .strip_prefix(".editing-")
+            .and_then(|pid| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let marker = path.path().display().to_string();
+        if !is_running(pid) {
+            let _ = fs::remove_file(&marker);
+            continue;
+        }
+        let contents = fs::read_to_string(&marker).unwrap_or_default();
+        if contents.lines().next() == Some(filename) {
+            markers.push((marker, pid));
+        }
+    }
+    markers
+}
+
+/// Returns the path of this process's editing marker.
+fn own_marker() -> String {
+    format!(
+        "{}/jrnl_folder/.editing-{}",
+        get_default_path(),
+        process::id()
+    )
+}
+
+/// Marks the file as open in the editor, until [`end_editing`] is called.
+pub fn start_editing(filename: &str) {
+    let _lock = lock_journal();
+    // Without a marker, only the warnings are lost, so this is not fatal
+    let _ = fs::write(own_marker(), format!("{}\n", filename));
+}
+
+/// Removes the editing marker, and warns about the lines added by other `jrnl`s while the
+/// editor was open, which the editor's save has overwritten.
+///
+/// # Sample output:
+/// ```text
+/// CONFLICT: /home/user/jrnl_folder/2026/2026_10.md was changed by another jrnl while it was open in the editor.
+/// These lines were added to it, but are no longer there:
+///   - 14:02-15:10 [work] code review
+/// Add them again in the entry.
+/// ```
+pub fn end_editing(filename: &str) {
+    let _lock = lock_journal();
+    let marker = own_marker();
+    let logged = fs::read_to_string(&marker).unwrap_or_default();
+    let _ = fs::remove_file(&marker);
+
+    let current = fs::read_to_string(filename).unwrap_or_default();
+    let current_lines: Vec<&str> = current.lines().map(str::trim).collect();
+    let lost: Vec<&str> = logged
+        .lines()
+        .skip(1)
+        .filter(|line| !current_lines.contains(&line.trim()))
+        .collect();
+    if lost.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{}: {} was changed by another jrnl while it was open in the editor.",
+        "CONFLICT".yellow().bold(),
+        filename.underline()
+    );
+    eprintln!("These lines were added to it, but are no longer there:");
+    for line in lost {
+        eprintln!("  {}", line.purple());
+    }
+    eprintln!("Add them again in the entry.");
+}
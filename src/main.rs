@@ -7,48 +7,113 @@
 //!
 //! This is made mostly for my own reference later on, when I will eventually need it.
 use crate::utils::*;
-use chrono::{DateTime, Datelike, Local};
-use clap::Parser;
+use chrono::{Datelike, NaiveTime};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::{ArgValueCompleter, CompleteEnv};
 use colored::Colorize;
-use funcs::{check_file_existed, inquire_date, read_config};
-use parse_datetime::parse_datetime_at_date as pdad;
+use funcs::{
+    check_file_existed, inquire_date, inquire_entry, inquire_note, inquire_search, inquire_tag,
+    read_config,
+};
 use shellexpand::tilde;
-use std::{path::Path, process};
+use std::{
+    fs,
+    path::{Component, Path},
+    process,
+};
 
+mod attachments;
+mod backup;
+mod check;
+mod complete;
+mod dates;
+mod export;
 mod funcs;
+mod import;
+mod layout;
+mod links;
+mod lock;
+mod merge;
+mod notes;
+mod onthisday;
+mod report;
+mod stats;
+mod sync;
+mod tags;
+mod timer;
+mod timesheet;
+mod todo;
+mod tui;
 mod utils;
+mod week;
 
 #[derive(Parser, Debug)]
-#[command(version, about, long_about=None)]
+#[command(name = "jrnl", version, about, long_about=None)]
 /// A simple tool to maintain a journal completely in CLI.
 /// Provides features like tags, to search by tag, generating reports
 /// for a given month, pre-filling some data(date, weekday, etc)
 struct Cli {
-    /// Optionally provide the date as YYYY-MM-DD or type `c` to open the calendar picker
+    /// Optionally provide the date as YYYY-MM-DD(or "yesterday", "last friday", etc) or type `c` to open the calendar picker
     /// , to open the relevant entry in the configured editor.
-    #[arg(default_missing_value=Some("c"), num_args=0..=1, group="main")]
+    #[arg(default_missing_value=Some("c"), num_args=0..=1, group="main",
+          add = ArgValueCompleter::new(complete::complete_dates))]
     open_entry: Option<String>,
 
-    /// Provide the date as YYYY-MM-DD, to fetch the relevant entry.
-    #[arg(short, long, default_missing_value=Some("a"), num_args=0..=1, group="main")]
+    /// Provide the date as YYYY-MM-DD(or "yesterday", "last friday", etc), to fetch the relevant entry.
+    /// Pick from all the entries if no date is provided.
+    #[arg(short, long, default_missing_value=Some("a"), num_args=0..=1, group="main",
+          add = ArgValueCompleter::new(complete::complete_dates))]
     entry: Option<String>,
 
-    /// List all occurances of a tag in a given file; Defaults to current month's file.
-    #[arg(short, long, groups = ["main", "yearmonth"])]
+    /// List all occurances of a tag in a given month or year; Defaults to the current month.
+    /// Pick from all the tags if no tag is provided.
+    #[arg(short, long, groups = ["main", "yearmonth", "scoped"], default_missing_value=Some(""), num_args=0..=1,
+          add = ArgValueCompleter::new(complete::complete_tags))]
     tag: Option<String>,
 
-    /// Search for a given string in a file; Defaults to current month's file.
-    #[arg(short, long, groups = ["main", "searching", "yearmonth"])]
+    /// Search for a given string in a given month or year; Defaults to the current month.
+    /// Asks for the string if not provided.
+    #[arg(short, long, groups = ["main", "searching", "yearmonth", "scoped"], default_missing_value=Some(""), num_args=0..=1)]
     search: Option<String>,
 
+    /// Where to look for the tag or the string(with `-t` or `-s`): the entries, the notes
+    /// (files opened with `--open`), or both; Defaults to the entries.
+    #[arg(long, requires = "scoped", value_parser = ["entries", "notes", "all"])]
+    scope: Option<String>,
+
     /// Provide a path to search for the directory `jrnl`.
     #[arg(short, long, default_missing_value=Some("."), num_args=0..=1)]
     path: Option<String>,
 
-    /// Generate a report about a given month's file; Defaults to current month's file.
+    /// Generate a report about a given month; Defaults to the current month.
     #[arg(long, groups = ["main", "yearmonth"])]
     gen_report: bool,
 
+    /// Generate the report for a week instead(with `--gen-report`); Defaults to this week.
+    /// Takes an ISO week(2026-W42), `last`, or any day in the week.
+    #[arg(long, requires = "gen_report", conflicts_with_all = ["year", "month"],
+          default_missing_value=Some("this week"), num_args=0..=1)]
+    week: Option<String>,
+
+    /// Generate the report for a quarter instead(with `--gen-report`); Defaults to this quarter.
+    /// Takes a quarter(2026-Q2), `last`, or any day or month in the quarter.
+    #[arg(long, requires = "gen_report", conflicts_with_all = ["year", "month", "week"],
+          default_missing_value=Some("this quarter"), num_args=0..=1)]
+    quarter: Option<String>,
+
+    /// Generate the report from this date(with `--gen-report`), until `--to` or today.
+    #[arg(long, requires = "gen_report", conflicts_with_all = ["year", "month", "week", "quarter"])]
+    from: Option<String>,
+
+    /// Generate the report until this date(with `--gen-report --from`).
+    #[arg(long, requires = "from")]
+    to: Option<String>,
+
+    /// Compare the report with the previous period(with `--gen-report`): the previous month,
+    /// week, quarter, year, or the same number of days before `--from`.
+    #[arg(long, requires = "gen_report")]
+    compare_previous: bool,
+
     /// Opens the configuration file: ~/.config/jrnl/config.toml
     #[arg(long, group = "main")]
     open_config: bool,
@@ -58,24 +123,299 @@ struct Cli {
     print_config: bool,
 
     /// Opens a file, in `jrnl_folder`, with any name, just to add some notes.
+    /// The name can have folders, like `projects/alpha.md`, which are made if needed.
     /// You can also use `--open e` to open `events.md` since it is a default file.
-    #[arg(long, group = "main")]
+    /// Pick from the existing files if no name is provided.
+    #[arg(long, group = "main", default_missing_value=Some(""), num_args=0..=1,
+          add = ArgValueCompleter::new(complete::complete_notes))]
     open: Option<String>,
 
-    /// Provide a year(YYYY) to search for a tag in, or to generate a report
+    /// Provide a year(YYYY, "last year") to search for a tag in, or to generate a report
     #[arg(short, long, requires = "yearmonth", default_missing_value=Some("0"), num_args=0..=1)]
-    year: Option<i32>,
+    year: Option<String>,
 
-    /// Provide the month(MM) to search for the tag in, or to generate a report
+    /// Provide the month(MM, "march", "last month", YYYY-MM) to search for the tag in, or to generate a report
     #[arg(short, long, requires = "yearmonth", default_missing_value=Some("0"), num_args=0..=1)]
-    month: Option<u32>,
+    month: Option<String>,
 
     /// Search for similar words as well, along with the current word.
     #[arg(short, long, requires = "searching", default_missing_value=Some("0"), num_args=0..=1)]
     approx: Option<u32>,
+
+    /// Open only the given date's entry in the editor(using a temporary file),
+    /// and splice it back into the journal file afterwards.
+    #[arg(short, long)]
+    isolated: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// List all tags used in the journal, with their count, and first and last use.
+    Tags {
+        /// Sort the tags by: count, name, first, last
+        #[arg(long, default_value = "count")]
+        sort: String,
+
+        #[command(subcommand)]
+        action: Option<TagsAction>,
+    },
+    /// Print the shell completions. For example, add this to your `~/.bashrc`:
+    /// `source <(jrnl completions bash)`
+    Completions {
+        /// The shell to print the completions for
+        #[arg(value_parser = ["bash", "zsh", "fish", "elvish", "nushell"])]
+        shell: String,
+    },
+    /// Print the man page. View it with `jrnl man | man -l -`
+    Man,
+    /// Browse the journal in a full-screen interactive interface.
+    Tui,
+    /// Compare two periods: tags, entries written, and their length.
+    /// For example: `jrnl compare 2026-09 2026-10`
+    Compare {
+        /// The earlier period: a month(2026-09), week(2026-W42), quarter(2026-Q3), year, etc.
+        before: String,
+
+        /// The later period
+        after: String,
+    },
+    /// Print statistics about the journal.
+    Stats {
+        #[command(subcommand)]
+        kind: StatsKind,
+    },
+    /// Add up the time spent, from records starting with a time range(`- 09:00-11:30 ...`)
+    /// or a duration(`- 45m ...`, `- 1h30 ...`), per day and per tag.
+    Timesheet {
+        /// From this date
+        #[arg(long, default_value = "this month")]
+        from: String,
+
+        /// Until this date; Defaults to today
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Only the records with this tag(or its children)
+        #[arg(long, add = ArgValueCompleter::new(complete::complete_tags))]
+        tag: Option<String>,
+
+        /// Export the records as CSV, to a file or to the terminal if no file is given
+        #[arg(long, default_missing_value=Some(""), num_args=0..=1)]
+        csv: Option<String>,
+    },
+    /// Start a timer for a record, like `jrnl start "[work] code review"`.
+    Start {
+        /// The record, with its tags
+        #[arg(required = true)]
+        record: Vec<String>,
+    },
+    /// Stop the running timer, and add its record with the time range to today's entry.
+    Stop,
+    /// Show the running timer.
+    Status,
+    /// Print the entries written on this day(or the given date) in previous years.
+    Onthisday {
+        /// The date; Defaults to today
+        #[arg(add = ArgValueCompleter::new(complete::complete_dates))]
+        date: Option<String>,
+
+        /// Which days to look at: the same day in previous years, the same week in
+        /// previous years, or 1 month, 6 months and 1 year ago
+        #[arg(long, default_value = "day", value_parser = ["day", "week", "ago"])]
+        window: String,
+    },
+    /// Copy a file(image, PDF, etc) into `jrnl_folder/attachments`, and link it in the entry.
+    Attach {
+        /// The file to attach
+        file: String,
+
+        /// The date of the entry; Defaults to today
+        #[arg(long, add = ArgValueCompleter::new(complete::complete_dates))]
+        date: Option<String>,
+    },
+    /// Export the entries into one Markdown file in a folder, along with their attachments.
+    Export {
+        /// The folder to export into, which is made if it does not exist
+        folder: String,
+
+        /// From this date
+        #[arg(long, default_value = "this month")]
+        from: String,
+
+        /// Until this date; Defaults to today
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Import the entries from other journaling tools, into the journal files.
+    /// Entries for dates already in the journal get only the records they do not have yet.
+    Import {
+        /// The format: the plain text of the Python jrnl, the JSON export of Day One,
+        /// a folder of daily notes(Obsidian, Logseq), or a CSV file
+        #[arg(value_parser = ["jrnl", "dayone", "daily", "csv"])]
+        format: String,
+
+        /// The file to import, or the folder for `daily`
+        path: String,
+
+        /// The column with the date, for `csv`
+        #[arg(long, default_value = "date")]
+        date_column: String,
+
+        /// The column with the text, for `csv`
+        #[arg(long, default_value = "text")]
+        text_column: String,
+
+        /// The column with the tags, for `csv`
+        #[arg(long, default_value = "tags")]
+        tags_column: String,
+    },
+    /// List the notes(files opened with `--open`), with when they were modified and their tags.
+    Notes,
+    /// List every entry and note linking(`[[...]]`) to a date or a note.
+    Backlinks {
+        /// The date(2026-10-01, yesterday, etc) or the note(projects/alpha)
+        #[arg(add = ArgValueCompleter::new(complete::complete_notes))]
+        target: String,
+    },
+    /// Look for problems in the journal, like links to entries or notes which do not exist.
+    Check,
+    /// Back up `jrnl_folder` and the config file into a compressed archive, and remove the
+    /// old backups as per `backup_keep_daily`, `backup_keep_weekly` and `backup_keep_monthly`.
+    Backup {
+        /// The folder to put the backup in; Defaults to `backup_folder` from the config
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Restore the journal from a backup, or only the entries of some dates.
+    /// A preview is shown before anything is changed.
+    Restore {
+        /// The backup: its path, or its name in `backup_folder`
+        archive: String,
+
+        /// Restore only these entries: a span(2026-10, 2026-W42, yesterday) or a range
+        /// (2026-10-01..2026-10-07)
+        #[arg(long, add = ArgValueCompleter::new(complete::complete_dates))]
+        date: Option<String>,
+    },
+    /// Merge this journal with another copy of it(like on a USB drive), entry by entry.
+    /// Entries changed in both since the last sync get conflict markers.
+    Sync {
+        /// The other `jrnl_folder`, or the folder containing it
+        other: String,
+    },
+    /// Combine the entries of one journal into another(like into an archive), putting the
+    /// entries of a date under one heading.
+    Merge {
+        /// The journal to merge from: a `jrnl_folder`, or the folder containing it(like a `--path`)
+        src: String,
+
+        /// The journal to merge into, which is made if it does not exist
+        dst: String,
+
+        /// Only print the changes, as a diff
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move the records with a tag(or its subtags) out of this journal into another one.
+    Split {
+        /// The tag of the records to move, like `work` for `[work]` and `[work/projx]`
+        #[arg(long, add = ArgValueCompleter::new(complete::complete_tags))]
+        tag: String,
+
+        /// The journal to move them into: a `jrnl_folder`, or the folder containing it
+        #[arg(long)]
+        into: String,
+
+        /// Only print the changes, as a diff
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move the entries into the files of another layout, and switch the configured `layout`.
+    Migrate {
+        /// monthly, daily, yearly, or a pattern like "{YYYY}/{MM}/week.md"
+        #[arg(long)]
+        layout: String,
+    },
+    /// Print every entry of a week, even across journal files.
+    Week {
+        /// The week: an ISO week(2026-W42), `last`, `next`, or any day in the week
+        #[arg(default_value = "this week")]
+        week: String,
+    },
+    /// List all open tasks(`- [ ] task`) in the journal.
+    Todo {
+        #[command(subcommand)]
+        action: Option<TodoAction>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum StatsKind {
+    /// Words per entry and per month, longest and shortest entries, and when you write.
+    Writing {
+        /// The period: a month(2026-09), week, quarter(2026-Q3), year, etc.
+        /// Defaults to the whole journal.
+        period: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TodoAction {
+    /// Mark a task as done.
+    Done {
+        /// The ID of the task, as shown by `jrnl todo`
+        id: usize,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TagsAction {
+    /// Rename a tag(and its subtags, like `work/projx` for `work`) in every journal file.
+    /// A preview is shown before anything is changed.
+    Rename {
+        /// The tag to be renamed
+        old: String,
+
+        /// The new name of the tag
+        new: String,
+    },
+    /// Merge several tags into one, in every journal file. A preview is shown before anything is changed.
+    Merge {
+        /// The tags to be merged
+        #[arg(required = true)]
+        tags: Vec<String>,
+
+        /// The tag to merge them into
+        #[arg(long)]
+        into: String,
+    },
 }
 
 fn main() {
+    // If called by the shell for completions, this completes and exits.
+    CompleteEnv::with_factory(Cli::command)
+        .var(complete::COMPLETE_VAR)
+        .shells(complete::SHELLS)
+        .complete();
+
+    let args = Cli::parse(); // Get args
+
+    // These are read by the shell and `man`, so nothing else must be printed.
+    match args.command {
+        Some(Commands::Completions { shell }) => {
+            complete::print_completions(&shell);
+            return;
+        }
+        Some(Commands::Man) => {
+            complete::print_man();
+            return;
+        }
+        _ => (),
+    }
+
     // First check if config is right
     if !Path::new(&tilde("~/.config/jrnl/config.toml").into_owned()).exists() {
         println!(
@@ -94,68 +434,161 @@ fn main() {
         println!("Continuing with default configuration.");
     }
 
-    let today: DateTime<Local> = Local::now(); //Get `now` time
+    let config = read_config().0;
+    if !config.timezone.is_empty() && dates::config_timezone().is_none() {
+        println!(
+            "{}: '{}' is not a known timezone. Continuing with the local timezone.\n{}: Use a name like \"Europe/Berlin\" or \"Asia/Kolkata\".",
+            "WARNING".yellow().bold(),
+            config.timezone,
+            "HELP".green().bold()
+        );
+    }
+    if !config.day_starts_at.is_empty()
+        && NaiveTime::parse_from_str(&config.day_starts_at, "%H:%M").is_err()
+    {
+        println!(
+            "{}: '{}' is not a valid time for `day_starts_at`. Continuing with midnight.\n{}: Use the HH:MM format, like \"04:00\".",
+            "WARNING".yellow().bold(),
+            config.day_starts_at,
+            "HELP".green().bold()
+        );
+    }
+
+    let today = dates::journal_today(); // Today's date, as per `timezone` and `day_starts_at`
 
     // Some variables to figure out whether both month and year were
     // provided or not; to check looping over a year
     let mut month_provided: bool = true;
     let mut year_provided: bool = true;
 
-    let args = Cli::parse(); // Get args
+    // Subcommands do their own thing, and ignore the rest of the arguments
+    if let Some(command) = args.command {
+        match command {
+            Commands::Tags { sort, action } => match action {
+                None => tags::print_tag_inventory(&sort),
+                Some(TagsAction::Rename { old, new }) => tags::rewrite_tags(&[old], &new),
+                Some(TagsAction::Merge { tags, into }) => tags::rewrite_tags(&tags, &into),
+            },
+            Commands::Completions { .. } | Commands::Man => (),
+            Commands::Tui => {
+                if let Err(e) = tui::run_tui() {
+                    eprintln!("{}", format!("An error occured: {}", e).red());
+                    process::exit(1);
+                }
+            }
+            Commands::Compare { before, after } => {
+                report::print_comparison(&dates::parse_span(&before), &dates::parse_span(&after))
+            }
+            Commands::Stats { kind } => match kind {
+                StatsKind::Writing { period } => {
+                    stats::print_writing_stats(period.as_deref().map(dates::parse_span))
+                }
+            },
+            Commands::Timesheet { from, to, tag, csv } => {
+                let span = dates::parse_range(&from, to.as_deref());
+                match csv {
+                    Some(filename) => {
+                        timesheet::export_timesheet_csv(&span, tag.as_deref(), &filename)
+                    }
+                    None => timesheet::print_timesheet(&span, tag.as_deref()),
+                }
+            }
+            Commands::Start { record } => timer::start_timer(&record.join(" ")),
+            Commands::Stop => timer::stop_timer(),
+            Commands::Status => timer::print_timer_status(),
+            Commands::Onthisday { date, window } => {
+                let date = match date {
+                    Some(date) => dates::parse_day(&date),
+                    None => dates::journal_today(),
+                };
+                onthisday::print_on_this_day(date, &window)
+            }
+            Commands::Attach { file, date } => {
+                let date = match date {
+                    Some(date) => dates::parse_day(&date),
+                    None => today,
+                };
+                attachments::attach_file(&file, date)
+            }
+            Commands::Export { folder, from, to } => {
+                export::export_entries(&dates::parse_range(&from, to.as_deref()), &folder)
+            }
+            Commands::Import {
+                format,
+                path,
+                date_column,
+                text_column,
+                tags_column,
+            } => import::import_entries(
+                &format,
+                &path,
+                &import::CsvColumns {
+                    date: date_column,
+                    text: text_column,
+                    tags: tags_column,
+                },
+            ),
+            Commands::Notes => notes::print_notes(),
+            Commands::Backlinks { target } => links::print_backlinks(&target),
+            Commands::Check => check::run_check(),
+            Commands::Migrate { layout } => layout::migrate(&layout),
+            Commands::Sync { other } => sync::sync(&other),
+            Commands::Merge { src, dst, dry_run } => merge::merge(&src, &dst, dry_run),
+            Commands::Split { tag, into, dry_run } => merge::split(&tag, &into, dry_run),
+            Commands::Backup { to } => backup::run_backup(to.as_deref()),
+            Commands::Restore { archive, date } => backup::restore(&archive, date.as_deref()),
+            Commands::Week { week } => week::print_week(&dates::parse_week(&week)),
+            Commands::Todo { action } => match action {
+                None => todo::print_open_tasks(),
+                Some(TodoAction::Done { id }) => todo::mark_task_done(id),
+            },
+        }
+        return;
+    }
 
     // Some match statements to figure out the Option<T>
     // Use `.as_deref()` to convert Option<String> to Option<&str>
     let args_entry = match args.entry.as_deref() {
         None => "",
         // Use inquire if no input for `-e`
-        Some("a") => &inquire_date().format("%Y-%m-%d").to_string(),
-        Some(entry) => &entry,
+        Some("a") => &inquire_entry().format("%Y-%m-%d").to_string(),
+        Some(entry) => entry,
     };
     let args_open_entry = match args.open_entry.as_deref() {
         None => &today.format("%Y-%m-%d").to_string(),
         Some("c") => &inquire_date().format("%Y-%m-%d").to_string(),
-        // Some cases to check to be able to use human relative time(yesterday, last week, etc)
-        Some(a) => {
-            let parts: Vec<&str> = a.split('-').collect();
-            if a.len() == 10 && parts[0].len() == 4 && parts[1].len() == 2 && parts[2].len() == 2 {
-                a
-            } else {
-                let pdad_result = pdad(today, a);
-                match pdad_result {
-                    Ok(value) => &value.naive_utc().format("%Y-%m-%d").to_string(),
-                    Err(e) => {
-                        println!("{}\nError: {}\n", "Couldn't understand your input".red(), e);
-                        &inquire_date().format("%Y-%m-%d").to_string()
-                    }
-                }
-            }
-        }
+        // Human relative time(yesterday, last week, etc) is understood by `parse_entry_args`
+        Some(a) => a,
     };
     let args_tag = match args.tag.as_deref() {
         None => "",
-        Some(a) => &a,
+        // Use inquire if no input for `-t`
+        Some("") => &inquire_tag(),
+        Some(tag) => tag,
     };
     let args_search = match args.search.as_deref() {
         None => "",
-        Some(a) => &a,
+        Some("") => &inquire_search(),
+        Some(word) => word,
     };
-    let args_tag_year = match args.year {
+    let (month_year, args_tag_month) = match args.month.as_deref() {
         None => {
-            year_provided = false;
-            today.year()
+            month_provided = false;
+            (None, today.month())
         }
-        // If we just pass `-y` with no <year> provided, we take the current year,
-        // but we have provided the year, so year_provided=true.
-        Some(0) => today.year(),
-        Some(year) => year,
+        Some("0") => (None, today.month()),
+        Some(month) => dates::parse_month(month),
     };
-    let args_tag_month = match args.month {
+    let args_tag_year = match args.year.as_deref() {
+        // The month might have its own year, like `-m "last month"` in January
         None => {
-            month_provided = false;
-            today.month()
+            year_provided = false;
+            month_year.unwrap_or(today.year())
         }
-        Some(0) => today.month(),
-        Some(month) => month,
+        // If we just pass `-y` with no <year> provided, we take the current year,
+        // but we have provided the year, so year_provided=true.
+        Some("0") => today.year(),
+        Some(year) => dates::parse_year(year),
     };
     let args_approx: u32 = match args.approx {
         None => 0,
@@ -164,12 +597,27 @@ fn main() {
     };
     let args_open = match args.open.as_deref() {
         None => "",
+        Some("") => &inquire_note(),
         Some("e") => "events.md",
-        Some(entry) => &entry,
+        Some(entry) => entry,
     };
 
+    let args_scope = args.scope.as_deref().unwrap_or("entries");
+    // The notes are looked in first, since `handle_tags` exits if nothing is found
+    if args_scope != "entries" {
+        let (word, search) = if args_tag.is_empty() {
+            (args_search, true)
+        } else {
+            (args_tag, false)
+        };
+        let found = notes::print_note_matches(word, search, args_approx);
+        if args_scope == "notes" {
+            process::exit(if found { 0 } else { 1 });
+        }
+    }
+
     // Handle arguments - not very efficiently or idiomatically
-    if args_tag != "" {
+    if !args_tag.is_empty() {
         handle_tags(
             args_tag,
             args_tag_year,
@@ -180,7 +628,7 @@ fn main() {
             args_approx,
         );
     }
-    if args_search != "" {
+    if !args_search.is_empty() {
         handle_tags(
             args_search,
             args_tag_year,
@@ -192,16 +640,16 @@ fn main() {
         );
     }
 
-    if args_entry != "" {
+    if !args_entry.is_empty() {
         let entry = get_entry(parse_entry_args(args_entry));
         println!("{}", entry);
     }
 
-    if args_open_entry != ""
-        && args_tag == ""
-        && args_search == ""
-        && args_entry == ""
-        && args_open == ""
+    if !args_open_entry.is_empty()
+        && args_tag.is_empty()
+        && args_search.is_empty()
+        && args_entry.is_empty()
+        && args_open.is_empty()
         && !args.open_config
         && !args.print_config
         && !args.gen_report
@@ -220,10 +668,37 @@ fn main() {
                 entry_date.yellow().bold()
             );
         }
-        open_editor(entry_date);
+        if args.isolated {
+            open_editor_isolated(entry_date);
+        } else {
+            open_editor(entry_date);
+        }
     }
 
-    if args_open != "" {
+    if !args_open.is_empty() {
+        // Notes may be nested, but never outside of `jrnl_folder`
+        if !Path::new(args_open)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            eprintln!(
+                "{}",
+                format!("The note {} is not in jrnl_folder.", args_open).red()
+            );
+            eprintln!(
+                "{}: Use a path inside jrnl_folder, like `projects/alpha.md`.",
+                "HELP".green().bold()
+            );
+            process::exit(1);
+        }
+        // Make the folders of nested notes(`projects/alpha.md`), if `jrnl_folder` is there
+        let jrnl_folder = format!("{}/jrnl_folder", get_default_path());
+        if let Some(parent) = Path::new(&format!("{}/{}", jrnl_folder, args_open)).parent()
+            && Path::new(&jrnl_folder).is_dir()
+            && let Err(e) = fs::create_dir_all(parent)
+        {
+            panic!("An error occured: {}", e);
+        }
         let has_file_existed = check_file_existed(&tilde(
             &format!("{}/jrnl_folder/{}", get_default_path(), args_open).to_owned(),
         ));
@@ -237,15 +712,32 @@ fn main() {
     }
 
     if args.gen_report {
-        if year_provided && !month_provided {
-            gen_report_year(args_tag_year);
+        let span = if let Some(week) = args.week.as_deref() {
+            dates::parse_week(week)
+        } else if let Some(quarter) = args.quarter.as_deref() {
+            dates::parse_quarter(quarter)
+        } else if let Some(from) = args.from.as_deref() {
+            dates::parse_range(from, args.to.as_deref())
+        } else if year_provided && !month_provided {
+            dates::DateSpan::year(args_tag_year)
         } else {
-            gen_report(args_tag_year, args_tag_month);
+            match dates::DateSpan::month(args_tag_year, args_tag_month) {
+                Some(span) => span,
+                None => {
+                    eprintln!("{}", "Invalid year/month provided.".red());
+                    process::exit(1);
+                }
+            }
+        };
+        report::gen_report(&span);
+        if args.compare_previous {
+            println!();
+            report::print_comparison(&span.previous(), &span);
         }
     }
 
     if args.open_config {
-        if !check_file_existed(&tilde("~/.config/jrnl/config.toml").into_owned()) {
+        if !check_file_existed(&tilde("~/.config/jrnl/config.toml")) {
             println!("Made config file: ~/.config/jrnl/config.toml");
         }
         process::Command::new(read_config().0.editor)
@@ -262,8 +754,12 @@ fn main() {
 /// Returns the path required in the current calling of the program.
 /// If a `--path` flag is passed, it takes the value of that, else
 /// it searches for the `default_path` in the config file.
+///
+/// When the arguments cannot be parsed(like when completing a half-typed command),
+/// the `default_path` is used.
 pub fn get_default_path() -> String {
-    match Cli::parse().path.as_deref() {
+    let path = Cli::try_parse().ok().and_then(|cli| cli.path);
+    match path.as_deref() {
         None => tilde(&read_config().0.default_path).into_owned(),
         Some(a) => a.to_string(),
     }
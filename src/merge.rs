@@ -0,0 +1,483 @@
+//! Contains `jrnl merge`, which combines one journal into another(like a personal and a
+//! work journal into an archive), and `jrnl split`, which moves the records with a tag out
+//! of this journal into another one.
+//!
+//! Both put the entries of a date under one heading, so a date never has two entries in
+//! the result, and records already in the other journal are not added twice. With
+//! `--dry-run`, the changes to every file are only printed, as a diff.
+use crate::attachments::*;
+use crate::backup::backup_before_rewrite;
+use crate::funcs::*;
+use crate::get_default_path;
+use crate::layout::*;
+use crate::lock::*;
+use crate::utils::*;
+use chrono::NaiveDate;
+use colored::Colorize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
+    path::Path,
+    process,
+};
+
+/// Returns the index of the line with the date(`# YYYY-MM-DD`) in an entry. The lines up to
+/// it are the heading of the entry.
+fn heading_end(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .position(|line| line.starts_with("# "))
+        .unwrap_or(0)
+}
+
+/// Splits the lines of an entry's records into the records, each with the indented lines
+/// under it. An empty line is a record of its own.
+fn split_records(lines: &[String]) -> Vec<Vec<String>> {
+    let mut records: Vec<Vec<String>> = Vec::new();
+    for line in lines {
+        let is_nested = line.starts_with([' ', '\t']) && !line.trim().is_empty();
+        match records.last_mut() {
+            Some(record) if is_nested && !record[0].trim().is_empty() => record.push(line.clone()),
+            _ => records.push(vec![line.clone()]),
+        }
+    }
+    records
+}
+
+/// Returns the record as compared between the journals, ignoring trailing whitespace.
+fn record_key(record: &[String]) -> String {
+    record
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+/// Combines the entries of a date in the destination with those from the source into one,
+/// under the heading of the first. A record of the source(with the lines indented under
+/// it) which is already in the destination is not added again. Nothing else is left out,
+/// so repeated lines within a journal's entries are kept.
+fn combine_entries(dst: &[Vec<String>], src: &[Vec<String>]) -> Vec<String> {
+    let Some(first) = dst.first().or(src.first()) else {
+        return Vec::new();
+    };
+    let end = heading_end(first);
+    let mut combined: Vec<String> = first[..=end].to_vec();
+    let mut in_dst: BTreeSet<String> = BTreeSet::new();
+    for lines in dst {
+        for record in split_records(&lines[heading_end(lines) + 1..]) {
+            in_dst.insert(record_key(&record));
+            combined.extend(record);
+        }
+    }
+    for lines in src {
+        for record in split_records(&lines[heading_end(lines) + 1..]) {
+            if !record[0].trim().is_empty() && in_dst.contains(&record_key(&record)) {
+                continue;
+            }
+            combined.extend(record);
+        }
+    }
+    while combined.len() > end + 1 && combined.last().is_some_and(|l| l.trim().is_empty()) {
+        combined.pop();
+    }
+    combined
+}
+
+/// Returns the `jrnl_folder` to write to: like [`resolve_jrnl_folder`], but it may not
+/// exist yet, in which case it is made when writing.
+fn resolve_target(path: &str) -> String {
+    let expanded = shellexpand::tilde(path).into_owned();
+    if Path::new(&expanded).exists() {
+        return resolve_jrnl_folder(path);
+    }
+    let expanded = expanded.trim_end_matches('/').to_string();
+    if expanded.ends_with("jrnl_folder") {
+        expanded
+    } else {
+        format!("{}/jrnl_folder", expanded)
+    }
+}
+
+/// Checks if the folder is this journal's `jrnl_folder`.
+fn is_this_journal(root: &str) -> bool {
+    let this = format!("{}/jrnl_folder", get_default_path());
+    fs::canonicalize(root).is_ok_and(|root| fs::canonicalize(this).is_ok_and(|this| this == root))
+}
+
+/// Takes the lock on the journal in the folder, as [`lock_journal`] does for this one.
+/// The lock on this journal is always taken, since writing any journal file takes it.
+fn lock_folder(root: &str) -> Option<File> {
+    if is_this_journal(root) {
+        return None;
+    }
+    lock_other_journal(root)
+}
+
+/// Takes the locks on the journals in the folders(each with the pattern of its layout) after
+/// the user has confirmed, and checks that their entries were not changed since they were
+/// read. Exits if they were, as the preview would no longer match what is written.
+///
+/// The locks are not held while waiting for the user, as that would block every other `jrnl`.
+fn lock_unchanged_folders(journals: &[(&str, &str, &Entries)]) -> (JournalLock, Vec<Option<File>>) {
+    let lock = lock_journal();
+    let locks = journals
+        .iter()
+        .map(|(root, _, _)| lock_folder(root))
+        .collect();
+    for (root, pattern, entries) in journals {
+        let current = if Path::new(root).is_dir() {
+            read_entries_in(root, pattern)
+        } else {
+            Entries::new()
+        };
+        if current != **entries {
+            eprintln!(
+                "{}",
+                format!(
+                    "{} was changed by another jrnl meanwhile. Nothing was changed; try again.",
+                    root
+                )
+                .red()
+            );
+            process::exit(1);
+        }
+    }
+    (lock, locks)
+}
+
+/// Prints the lines changed between the old and new contents of a file, with their line
+/// numbers, like the preview of `jrnl tags rename`.
+///
+/// # Sample output:
+/// Note that removed lines are colored in red, and added lines in green.
+///
+/// ```text
+/// archive/jrnl_folder/2026/2026_10.md
+///    12 + - [work/projx] kickoff
+///    13 + - [work] code review
+/// ```
+fn print_diff(filename: &str, old: &str, new: &str) {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // The lines which are the same at the start and the end are skipped
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // The longest common subsequence of the rest, from the end
+    let (n, m) = (old_mid.len(), new_mid.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    println!("{}", filename.underline().bold());
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            let line = format!("+ {}", new_mid[j]);
+            println!("{:>5} {}", prefix + j + 1, line.green());
+            j += 1;
+        } else {
+            let line = format!("- {}", old_mid[i]);
+            println!("{:>5} {}", prefix + i + 1, line.red());
+            i += 1;
+        }
+    }
+}
+
+/// Prints the diff of every journal file(in the folder, a `jrnl_folder`, in the layout of
+/// the pattern) holding the dates.
+fn print_changes(root: &str, pattern: &str, entries: &Entries, dates: &[NaiveDate]) {
+    for (file, contents) in entries_contents(entries, pattern, dates) {
+        let filename = format!("{}/{}", root, file);
+        let old = fs::read_to_string(&filename).unwrap_or_default();
+        if old != contents {
+            print_diff(&filename, &old, &contents);
+        }
+    }
+}
+
+/// Combines every entry of the journal `src` into the journal `dst`(each a `jrnl_folder`,
+/// or the folder containing it, like a `--path`), after asking for confirmation.
+/// `dst` is made if it does not exist, and `src` is not changed.
+///
+/// With `dry_run`, the changes are only printed as a diff.
+pub fn merge(src: &str, dst: &str, dry_run: bool) {
+    let src_root = resolve_jrnl_folder(src);
+    let dst_root = resolve_target(dst);
+    if fs::canonicalize(&src_root).ok() == fs::canonicalize(&dst_root).ok() {
+        eprintln!("{}", "Both journals are the same folder.".red());
+        process::exit(1);
+    }
+
+    // Each journal is in its own layout
+    let src_pattern = folder_layout(&src_root);
+    let dst_pattern = folder_layout(&dst_root);
+    // As read, to check that they are unchanged when writing
+    let src_read = read_entries_in(&src_root, &src_pattern);
+    let dst_read = if Path::new(&dst_root).is_dir() {
+        read_entries_in(&dst_root, &dst_pattern)
+    } else {
+        Entries::new()
+    };
+    let src_entries = relink_entries(&src_read, &src_pattern, &dst_pattern);
+    let mut dst_entries = dst_read.clone();
+    if src_entries.is_empty() {
+        eprintln!("{}", format!("There are no entries in {}.", src_root).red());
+        process::exit(1);
+    }
+
+    let mut changed: Vec<NaiveDate> = Vec::new();
+    for (date, entries) in src_entries.iter() {
+        let dst = dst_entries.get(date).cloned().unwrap_or_default();
+        let combined = vec![combine_entries(&dst, entries)];
+        if dst_entries.get(date) != Some(&combined) {
+            dst_entries.insert(*date, combined);
+            changed.push(*date);
+        }
+    }
+    let dst_attachments = get_attachment_files_in(&dst_root);
+    let attachments: Vec<String> = get_attachment_files_in(&src_root)
+        .into_iter()
+        .filter(|a| !dst_attachments.contains(a))
+        .collect();
+
+    if changed.is_empty() && attachments.is_empty() {
+        println!(
+            "{}",
+            format!("{} already has every entry of {}.", dst_root, src_root).green()
+        );
+        return;
+    }
+    if dry_run {
+        print_changes(&dst_root, &dst_pattern, &dst_entries, &changed);
+        println!(
+            "\n{} entries and {} attachment(s) would be merged into {}. Nothing was changed.",
+            changed.len().to_string().bold(),
+            attachments.len().to_string().bold(),
+            dst_root.underline()
+        );
+        return;
+    }
+    println!(
+        "{} entries and {} attachment(s) will be merged into {}.",
+        changed.len().to_string().bold(),
+        attachments.len().to_string().bold(),
+        dst_root.underline()
+    );
+    if !inquire_confirm("Merge the journals?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Held until everything is written, so that no change made meanwhile is lost
+    let _locks = lock_unchanged_folders(&[
+        (&src_root, &src_pattern, &src_read),
+        (&dst_root, &dst_pattern, &dst_read),
+    ]);
+    if is_this_journal(&dst_root) {
+        backup_before_rewrite();
+    }
+    write_entries_in(&dst_root, &dst_pattern, &dst_entries, &changed);
+    copy_attachments(&src_root, &dst_root, &attachments);
+    println!("{}", "Done.".green());
+}
+
+/// Returns which lines of an entry's records have the tag: the records with it, along with
+/// the indented lines under them.
+fn tagged_lines(lines: &[&str], tag: &str) -> BTreeSet<usize> {
+    let mut tagged: BTreeSet<usize> = BTreeSet::new();
+    let mut moving = false;
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            moving = false;
+        } else if !line.starts_with([' ', '\t']) {
+            moving = get_tags_from_line(line)
+                .iter()
+                .any(|found| tag_matches(found, tag));
+        }
+        if moving {
+            tagged.insert(i);
+        }
+    }
+    tagged
+}
+
+/// Moves the records with the tag(or a subtag of it, like `work/projx` for `work`) from
+/// this journal into the journal `into`(a `jrnl_folder`, or the folder containing it),
+/// after asking for confirmation. `into` is made if it does not exist. Entries left without
+/// records are removed, and the attachments linked in the records are copied.
+///
+/// With `dry_run`, the changes are only printed as a diff.
+pub fn split(tag: &str, into: &str, dry_run: bool) {
+    let tag = tag.trim_matches(['[', ']']);
+    let root = format!("{}/jrnl_folder", get_default_path());
+    if !Path::new(&root).is_dir() {
+        eprintln!(
+            "{}",
+            "There doesn't seem to be a `jrnl_folder`. Please create it.".red()
+        );
+        process::exit(1);
+    }
+    let into_root = resolve_target(into);
+    if is_this_journal(&into_root) {
+        eprintln!("{}", "Both journals are the same folder.".red());
+        process::exit(1);
+    }
+
+    let pattern = config_layout();
+    let into_pattern = folder_layout(&into_root);
+    // As read, to check that they are unchanged when writing
+    let into_read = if Path::new(&into_root).is_dir() {
+        read_entries_in(&into_root, &into_pattern)
+    } else {
+        Entries::new()
+    };
+    let mut into_entries = into_read.clone();
+    let mut read_files: Vec<(String, String)> = Vec::new();
+
+    // The files of this journal are changed in place, keeping the order of their entries
+    // (file relative to `jrnl_folder`, old contents, new contents)
+    let mut changed_files: Vec<(String, String, String)> = Vec::new();
+    // Date -> the entry of the records moved from it
+    let mut moved_entries: BTreeMap<NaiveDate, Vec<String>> = BTreeMap::new();
+    let mut no_of_records = 0;
+    let mut attachments: BTreeSet<String> = BTreeSet::new();
+    for (_, _, filename) in get_journal_files_in(&root, &pattern) {
+        let contents = match fs::read_to_string(&filename) {
+            Ok(data) => data,
+            Err(e) => panic!("Error reading file: {}", e),
+        };
+        read_files.push((filename.clone(), contents.clone()));
+        let lines: Vec<&str> = contents.lines().collect();
+        // Lines which are moved, or removed with an entry left without records
+        let mut removed: BTreeSet<usize> = BTreeSet::new();
+        for (start, heading, end) in entry_ranges(&lines) {
+            let Ok(date) = NaiveDate::parse_from_str(lines[heading][1..].trim(), "%Y-%m-%d") else {
+                continue;
+            };
+            let body = &lines[heading + 1..end];
+            let tagged = tagged_lines(body, tag);
+            if tagged.is_empty() {
+                continue;
+            }
+            let moved: Vec<String> = tagged.iter().map(|i| body[*i].to_string()).collect();
+            no_of_records += moved
+                .iter()
+                .filter(|line| !line.starts_with([' ', '\t']))
+                .count();
+            for line in moved.iter() {
+                attachments.extend(get_attachments_from_line(line));
+            }
+            let moved_entry = moved_entries.entry(date).or_insert_with(|| {
+                lines[start..=heading]
+                    .iter()
+                    .skip_while(|line| line.trim().is_empty())
+                    .map(|line| line.to_string())
+                    .collect()
+            });
+            moved_entry.extend(moved);
+            // An entry left without records is removed
+            let is_left_empty = body
+                .iter()
+                .enumerate()
+                .all(|(i, line)| tagged.contains(&i) || line.trim().is_empty());
+            if is_left_empty {
+                removed.extend(start..end);
+            } else {
+                removed.extend(tagged.iter().map(|i| heading + 1 + i));
+            }
+        }
+        let file_changed = !removed.is_empty();
+        let mut new_contents: String = lines
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !removed.contains(i))
+            .map(|(_, line)| format!("{}\n", line))
+            .collect();
+        if !contents.ends_with('\n') {
+            new_contents.pop();
+        }
+        if file_changed {
+            let file = filename[root.len() + 1..].to_string();
+            changed_files.push((file, contents, new_contents));
+        }
+    }
+    let changed: Vec<NaiveDate> = moved_entries.keys().cloned().collect();
+    for (date, moved_entry) in moved_entries {
+        let into = into_entries.get(&date).cloned().unwrap_or_default();
+        let moved_entry = relink_attachments(
+            &moved_entry,
+            &journal_path(&pattern, date),
+            &journal_path(&into_pattern, date),
+        );
+        into_entries.insert(date, vec![combine_entries(&into, &[moved_entry])]);
+    }
+    let into_attachments = get_attachment_files_in(&into_root);
+    let attachments: Vec<String> = attachments
+        .into_iter()
+        .filter(|a| !into_attachments.contains(a) && Path::new(&format!("{}/{}", root, a)).exists())
+        .collect();
+
+    if changed.is_empty() {
+        eprintln!(
+            "{}",
+            format!("No records found with the tag: {}", tag.cyan()).red()
+        );
+        process::exit(1);
+    }
+    if dry_run {
+        for (file, old, new) in changed_files.iter() {
+            print_diff(&format!("{}/{}", root, file), old, new);
+        }
+        print_changes(&into_root, &into_pattern, &into_entries, &changed);
+        println!(
+            "\n{} record(s) from {} entries would be moved into {}. Nothing was changed.",
+            no_of_records.to_string().bold(),
+            changed.len().to_string().bold(),
+            into_root.underline()
+        );
+        return;
+    }
+    println!(
+        "{} record(s) from {} entries will be moved into {}.",
+        no_of_records.to_string().bold(),
+        changed.len().to_string().bold(),
+        into_root.underline()
+    );
+    if !inquire_confirm("Split the journal?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Held until everything is written, so that no change made meanwhile is lost
+    let _lock = lock_unchanged(&read_files);
+    let _into_locks = lock_unchanged_folders(&[(&into_root, &into_pattern, &into_read)]);
+    backup_before_rewrite();
+    // Written first, so that the records are never only in the journal being removed from
+    write_entries_in(&into_root, &into_pattern, &into_entries, &changed);
+    copy_attachments(&root, &into_root, &attachments);
+    for (file, _, contents) in changed_files {
+        write_journal_file_in(&root, &file, &contents);
+    }
+    println!("{}", "Done.".green());
+}
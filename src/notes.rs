@@ -0,0 +1,207 @@
+//! Contains the notes: the files in `jrnl_folder` other than the journal files, opened with
+//! `--open`(like `--open projects/alpha.md`).
+//!
+//! `jrnl notes` lists them, and `-t`/`-s` look in them with `--scope notes` or `--scope all`.
+use crate::dates::journal_datetime;
+use crate::funcs::*;
+use crate::get_default_path;
+use crate::utils::*;
+use chrono::NaiveDateTime;
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use std::{fs, process};
+use stringmetrics::levenshtein;
+
+/// Returns the path of the note, whose name is relative to `jrnl_folder`.
+pub fn note_path(note: &str) -> String {
+    format!("{}/jrnl_folder/{}", get_default_path(), note)
+}
+
+/// Returns when the note was last modified, in the configured `timezone`.
+fn note_modified(note: &str) -> Option<NaiveDateTime> {
+    let modified = fs::metadata(note_path(note)).ok()?.modified().ok()?;
+    Some(journal_datetime(modified.into()))
+}
+
+/// Returns the tags used in the note, sorted and without repetitions.
+/// Files which are not text(images, etc) have no tags, and the dates of the events
+/// in `events.md`(`- [10-20] ...`) are not tags either.
+pub fn get_note_tags(note: &str) -> Vec<String> {
+    let contents = fs::read_to_string(note_path(note)).unwrap_or_default();
+    let mut tags: Vec<String> = contents
+        .lines()
+        .flat_map(get_tags_from_line)
+        .filter(|tag| note != "events.md" || !tag.chars().all(|c| c.is_ascii_digit() || c == '-'))
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Prints all the notes, with when they were last modified and their tags, latest first.
+///
+/// # Sample output:
+/// Note that the headings are colored in green.
+///
+/// ```text
+/// ╭───────────────────┬──────────────────┬──────────────────╮
+/// │ Note              ┆ Modified         ┆ Tags             │
+/// ╞═══════════════════╪══════════════════╪══════════════════╡
+/// │ projects/alpha.md ┆ 2026-10-17 21:40 ┆ work, work/projx │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ events.md         ┆ 2026-10-02 09:12 ┆                  │
+/// ╰───────────────────┴──────────────────┴──────────────────╯
+/// ```
+pub fn print_notes() {
+    let mut notes: Vec<(String, Option<NaiveDateTime>)> = get_note_files()
+        .into_iter()
+        .map(|note| {
+            let modified = note_modified(&note);
+            (note, modified)
+        })
+        .collect();
+    if notes.is_empty() {
+        eprintln!(
+            "{}",
+            "There are no notes in `jrnl_folder`. Make one with `jrnl --open <name>`.".red()
+        );
+        process::exit(1);
+    }
+    notes.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Note".green(), "Modified".green(), "Tags".green()]);
+    for (note, modified) in notes.iter() {
+        let modified = match modified {
+            Some(time) => time.format("%Y-%m-%d %H:%M").to_string(),
+            None => String::new(),
+        };
+        table.add_row(vec![
+            note.cyan().to_string(),
+            modified,
+            get_note_tags(note).join(", "),
+        ]);
+    }
+    print_with_pager(&format!("{}", table), notes.len());
+}
+
+/// Returns the line with every occurance of the word colored.
+fn highlight(line: &str, word: &str) -> String {
+    let lower = line.to_lowercase();
+    let word_lower = word.to_lowercase();
+    // Lowercasing can change the length of some characters, so it is not highlighted then
+    if word.is_empty() || lower.len() != line.len() {
+        return line.to_string();
+    }
+    let mut result = String::new();
+    let mut start = 0;
+    while let Some(pos) = lower[start..].find(&word_lower) {
+        result.push_str(&line[start..start + pos]);
+        result.push_str(
+            &line[start + pos..start + pos + word.len()]
+                .purple()
+                .to_string(),
+        );
+        start += pos + word.len();
+    }
+    result.push_str(&line[start..]);
+    result
+}
+
+/// Returns the lines in the notes with the tag(or one of its children), or with the word
+/// if `search` is true, as (note, line number, line) with the matches colored.
+///
+/// With `approx`, words within that [`levenshtein`] distance of the word match as well.
+pub fn search_notes(word: &str, search: bool, approx: u32) -> Vec<(String, usize, String)> {
+    let mut matches: Vec<(String, usize, String)> = Vec::new();
+    for note in get_note_files() {
+        // Only text files can be searched
+        let Ok(contents) = fs::read_to_string(note_path(&note)) else {
+            continue;
+        };
+        for (i, line) in contents.lines().enumerate() {
+            let record = line.trim().trim_start_matches("- ").trim();
+            if search {
+                let mut highlighted = highlight(record, word);
+                let mut found = record.to_lowercase().contains(&word.to_lowercase());
+                if !found && approx > 0 {
+                    for thing in record.split(&[' ', '(', ')', ',', '.', ';', '-', '|', '/'][..]) {
+                        if !thing.is_empty()
+                            && levenshtein(&thing.to_lowercase(), &word.to_lowercase()) <= approx
+                        {
+                            highlighted = highlight(record, thing);
+                            found = true;
+                            break;
+                        }
+                    }
+                }
+                if found {
+                    matches.push((note.clone(), i + 1, highlighted));
+                }
+            } else {
+                let mut tags: Vec<String> = get_tags_from_line(line)
+                    .into_iter()
+                    .filter(|tag| tag_matches(tag, word))
+                    .collect();
+                if tags.is_empty() {
+                    continue;
+                }
+                tags.dedup();
+                let mut highlighted = record.to_string();
+                for tag in tags.iter() {
+                    highlighted =
+                        highlighted.replace(&format!("[{}]", tag), &format!("[{}]", tag.cyan()));
+                }
+                matches.push((note.clone(), i + 1, highlighted));
+            }
+        }
+    }
+    matches
+}
+
+/// Prints the lines in the notes with the tag, or the word if `search` is true.
+/// Returns whether anything was found.
+///
+/// # Sample output:
+/// Note that the headings are colored in green.
+///
+/// ```text
+/// ╭───────────────────┬──────┬──────────────────────────────╮
+/// │ Note              ┆ Line ┆ Record                       │
+/// ╞═══════════════════╪══════╪══════════════════════════════╡
+/// │ projects/alpha.md ┆ 4    ┆ [work/projx] draft the specs │
+/// ╰───────────────────┴──────┴──────────────────────────────╯
+/// ```
+pub fn print_note_matches(word: &str, search: bool, approx: u32) -> bool {
+    let matches = search_notes(word, search, approx);
+    if matches.is_empty() {
+        if search {
+            println!("No matches for '{}' found in the notes", word.purple());
+        } else {
+            println!(
+                "No matches for the tag '{}' found in the notes",
+                word.cyan()
+            );
+        }
+        return false;
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Note".green(), "Line".green(), "Record".green()]);
+    for (note, line_no, line) in matches.iter() {
+        table.add_row(vec![
+            note.cyan().to_string(),
+            line_no.to_string(),
+            line.clone(),
+        ]);
+    }
+    print_with_pager(&format!("{}", table), matches.len());
+    true
+}
@@ -0,0 +1,126 @@
+//! Contains the retrospective view, `jrnl onthisday`: the entries written on the same day
+//! in previous years, or some time ago.
+use crate::funcs::*;
+use crate::utils::*;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use colored::Colorize;
+use std::collections::HashMap;
+
+/// Returns how long ago `past` was from `date`, like "1 year ago" or "6 months ago".
+fn time_ago(past: NaiveDate, date: NaiveDate) -> String {
+    let days = (date - past).num_days();
+    let (value, unit) = if days >= 360 {
+        ((days as f64 / 365.25).round() as i64, "year")
+    } else if days >= 28 {
+        ((days as f64 / 30.44).round() as i64, "month")
+    } else {
+        (days, "day")
+    };
+    format!(
+        "{} {}{} ago",
+        value,
+        unit,
+        if value == 1 { "" } else { "s" }
+    )
+}
+
+/// Returns the dates with an entry in the window around `date`, with their first record,
+/// as (date, first record), latest first.
+///
+/// The window is one of:
+/// - "day": the same month and day in every previous year
+/// - "week": the same week(3 days before and after) in every previous year
+/// - "ago": 1 month, 6 months and 1 year ago
+pub fn on_this_day(date: NaiveDate, window: &str) -> Vec<(NaiveDate, String)> {
+    let entries: HashMap<NaiveDate, String> = get_all_entries()
+        .into_iter()
+        .filter_map(|(day, first)| {
+            let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?;
+            Some((day, first))
+        })
+        .collect();
+    let first_year = match entries.keys().min() {
+        Some(first) => first.year(),
+        None => return Vec::new(),
+    };
+
+    let mut dates: Vec<NaiveDate> = Vec::new();
+    match window {
+        "ago" => {
+            for months in [1, 6, 12] {
+                if let Some(past) = date.checked_sub_months(Months::new(months)) {
+                    dates.push(past);
+                }
+            }
+        }
+        _ => {
+            let days = if window == "week" { 3 } else { 0 };
+            for year in (first_year..date.year()).rev() {
+                // 29th February is remembered on 28th February, in the other years
+                let Some(past) = date
+                    .with_year(year)
+                    .or_else(|| date.pred_opt()?.with_year(year))
+                else {
+                    continue;
+                };
+                for offset in -days..=days {
+                    dates.push(past + Duration::days(offset));
+                }
+            }
+        }
+    }
+
+    dates
+        .into_iter()
+        .filter_map(|day| Some((day, entries.get(&day)?.clone())))
+        .collect()
+}
+
+/// Prints the entries written on the same day(or in the window) in the previous years.
+pub fn print_on_this_day(date: NaiveDate, window: &str) {
+    let matches = on_this_day(date, window);
+    let mut output = format!(
+        "{}\n\n",
+        format!("On this day: {}", date.format("%Y-%m-%d %A"))
+            .bold()
+            .cyan()
+            .underline()
+    );
+    if matches.is_empty() {
+        output.push_str("Nothing was written on this day before.\n");
+    }
+    for (day, _) in matches.iter() {
+        output.push_str(&format!(
+            "{}\n{}\n",
+            format!("── {} ──", time_ago(*day, date)).purple().bold(),
+            get_entry(*day)
+        ));
+    }
+    print_with_pager(&output, output.lines().count());
+}
+
+/// Returns the section of `--gen-report` listing the entries on this day in the previous years,
+/// with their first record. Empty if there are none.
+///
+/// # Sample output:
+/// ```text
+/// On this day:
+/// [2025-10-18] 1 year ago: Went hiking with [family]
+/// [2023-10-18] 3 years ago: First day at the new job [work]
+/// ```
+pub fn on_this_day_section(date: NaiveDate) -> String {
+    let matches = on_this_day(date, "day");
+    if matches.is_empty() {
+        return String::new();
+    }
+    let mut output = format!("{}\n", "On this day:".yellow().bold());
+    for (day, first) in matches {
+        output.push_str(&format!(
+            "[{}] {}: {}\n",
+            day.format("%Y-%m-%d").to_string().cyan(),
+            time_ago(day, date),
+            first
+        ));
+    }
+    output
+}
@@ -0,0 +1,600 @@
+//! Contains the report engine, used by `--gen-report` for months, years, quarters, weeks
+//! and any range of dates(`--from`/`--to`).
+//!
+//! Everything is collected once from the journal files overlapping the span([`collect_report`]),
+//! and then printed the same way whatever the span.
+use crate::dates::{DateSpan, SpanKind, journal_today, weekdays_header};
+use crate::funcs::*;
+use crate::layout::{get_journal_files, get_journal_files_between};
+use crate::todo::get_all_tasks;
+use crate::utils::*;
+use crate::week::week_title;
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveTime};
+use colored::Colorize;
+use comfy_table::{
+    ContentArrangement, Table,
+    modifiers::UTF8_ROUND_CORNERS,
+    presets::{NOTHING, UTF8_FULL},
+};
+use std::{collections::HashMap, fs, process};
+
+/// Everything the report is made of, for a span of dates.
+#[derive(Debug, Default)]
+pub struct ReportData {
+    /// Dates which have an entry, sorted
+    pub entries: Vec<NaiveDate>,
+
+    /// How often each tag has been used
+    pub tags: HashMap<String, u32>,
+
+    /// The food records, as [breakfast, lunch, dinner, other]
+    pub food: Vec<Vec<String>>,
+
+    /// Number of words written in the records
+    pub words: usize,
+
+    /// Length and writing time of each entry
+    pub entry_stats: Vec<EntryStats>,
+}
+
+/// The length and writing time of a single entry.
+#[derive(Debug, Clone)]
+pub struct EntryStats {
+    /// Date of the entry
+    pub date: NaiveDate,
+
+    /// Number of words in its records(without the tags)
+    pub words: usize,
+
+    /// Number of records(`- ...`)
+    pub records: usize,
+
+    /// Time from the `### DAY (HH:MM:SS)` header, if `add_timestamp` was on
+    pub time: Option<NaiveTime>,
+}
+
+/// Returns the months(year, month) overlapping the span, in order.
+fn months_in_span(span: &DateSpan) -> Vec<(i32, u32)> {
+    let mut months: Vec<(i32, u32)> = Vec::new();
+    let mut first_day = span.start.with_day(1).unwrap();
+    while first_day <= span.end {
+        months.push((first_day.year(), first_day.month()));
+        first_day = first_day + Months::new(1);
+    }
+    months
+}
+
+/// Goes over the journal files overlapping the span, and collects everything
+/// within the span.
+pub fn collect_report(span: &DateSpan) -> ReportData {
+    let mut data = ReportData::default();
+    for (first, last, filename) in get_journal_files() {
+        if first > span.end || last < span.start {
+            continue;
+        }
+        let contents = fs::read_to_string(&filename).unwrap_or_default();
+        let mut in_span = false;
+        let mut time: Option<NaiveTime> = None;
+        for line in contents.lines() {
+            if let Some(sub_heading) = line.strip_prefix("### ") {
+                // `### FRIDAY (14:02:10)` or `### (14:02:10)`
+                time = sub_heading
+                    .split(['(', ')'])
+                    .find_map(|part| NaiveTime::parse_from_str(part.trim(), "%H:%M:%S").ok());
+                continue;
+            }
+            if let Some(heading) = line.strip_prefix("# ") {
+                in_span = match NaiveDate::parse_from_str(heading.trim(), "%Y-%m-%d") {
+                    Ok(date) if date >= span.start && date <= span.end => {
+                        data.entries.push(date);
+                        data.entry_stats.push(EntryStats {
+                            date,
+                            words: 0,
+                            records: 0,
+                            time,
+                        });
+                        true
+                    }
+                    _ => false,
+                };
+                time = None;
+                continue;
+            }
+            if !in_span {
+                continue;
+            }
+            for tag in get_tags_from_line(line) {
+                *data.tags.entry(tag).or_insert(0) += 1;
+            }
+            let words = line
+                .split_whitespace()
+                .filter(|word| !word.starts_with('[') && word.chars().any(char::is_alphanumeric))
+                .count();
+            data.words += words;
+            if let Some(entry) = data.entry_stats.last_mut() {
+                entry.words += words;
+                // Skip the empty food column, like `get_all_entries`
+                if line.starts_with('-') && words > 0 {
+                    entry.records += 1;
+                }
+            }
+            if let Some(food) = line.trim().strip_prefix("- [food]") {
+                data.food
+                    .push(food.split('|').map(|s| s.trim().to_string()).collect());
+            }
+        }
+    }
+    data.entries.sort();
+    data.entry_stats.sort_by_key(|entry| entry.date);
+    // The same date can have more than one heading
+    data.entries.dedup();
+    data.entry_stats.dedup_by(|later, first| {
+        if later.date != first.date {
+            return false;
+        }
+        first.words += later.words;
+        first.records += later.records;
+        first.time = first.time.or(later.time);
+        true
+    });
+    data
+}
+
+/// Returns the name of the span, as used in the title of the report.
+pub fn span_title(span: &DateSpan) -> String {
+    match span.kind {
+        SpanKind::Month => format!(
+            "{}, {}",
+            month_no_to_name(span.start.month()),
+            span.start.year()
+        ),
+        SpanKind::Year => span.start.year().to_string(),
+        SpanKind::Quarter => format!(
+            "Q{} {} ({} to {})",
+            (span.start.month() - 1) / 3 + 1,
+            span.start.year(),
+            month_no_to_name(span.start.month()),
+            month_no_to_name(span.end.month())
+        ),
+        SpanKind::Week => week_title(span),
+        SpanKind::Day | SpanKind::Range => format!(
+            "{} to {}",
+            span.start.format("%Y-%m-%d"),
+            span.end.format("%Y-%m-%d")
+        ),
+    }
+}
+
+/// Returns the calendars of the span, with the days having an entry highlighted.
+///
+/// A week is a single row of days, a single month is one calendar, and anything longer is
+/// a grid of calendars, as wide as the terminal.
+fn span_calendar(span: &DateSpan, entries: &[NaiveDate]) -> String {
+    if span.kind == SpanKind::Week {
+        let mut days = String::new();
+        let mut day = span.start;
+        while day <= span.end {
+            if entries.contains(&day) {
+                days.push_str(&format!("{:>2} ", day.day().to_string().green().bold()));
+            } else {
+                days.push_str(&format!("{:>2} ", day.day()));
+            }
+            day += Duration::days(1);
+        }
+        return format!("{}\n{}\n", weekdays_header().bright_yellow(), days);
+    }
+
+    let mut calendars: Vec<String> = Vec::new();
+    for (year, month) in months_in_span(span) {
+        let days: Vec<u32> = entries
+            .iter()
+            .filter(|date| date.year() == year && date.month() == month)
+            .map(|date| date.day())
+            .collect();
+        calendars.push(print_calendar(year, month, days));
+    }
+    if calendars.len() == 1 {
+        return calendars.remove(0);
+    }
+
+    // Width to get the number of columns to push to the table when
+    // making the calendar grid
+    let (w, _h) = match term_size::dimensions() {
+        Some((w, h)) => (w, h),
+        None => (100, 30),
+    };
+    let w = (w / 23).max(1); // Each calendar takes 23 chars
+    let mut cal = Table::new();
+    cal.set_content_arrangement(ContentArrangement::Dynamic)
+        .load_preset(NOTHING);
+    for row in calendars.chunks(w) {
+        cal.add_row(row);
+    }
+    cal.to_string()
+}
+
+/// Makes the summary of the food records: how many times each meal was logged,
+/// and what was eaten the most.
+///
+/// # Sample output:
+/// Note that the headings are colored in green
+///
+/// ```text
+/// ╭───────────┬──────────────┬─────────────╮
+/// │ Meal      ┆ Times logged ┆ Most common │
+/// ╞═══════════╪══════════════╪═════════════╡
+/// │ Breakfast ┆ 12           ┆ eggs (5)    │
+/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ Lunch     ┆ 10           ┆ rice (4)    │
+/// ╰───────────┴──────────────┴─────────────╯
+/// ```
+fn make_food_summary(food: &[Vec<String>]) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Meal".green(),
+            "Times logged".green(),
+            "Most common".green(),
+        ]);
+    for (i, meal) in ["Breakfast", "Lunch", "Dinner", "Other"].iter().enumerate() {
+        let mut freq_map: HashMap<String, u32> = HashMap::new();
+        for item in food.iter().filter_map(|record| record.get(i)) {
+            if !item.is_empty() {
+                *freq_map.entry(item.to_lowercase()).or_insert(0) += 1;
+            }
+        }
+        let days: u32 = freq_map.values().sum();
+        let most_common = freq_map
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(item, count)| format!("{} ({})", item, count))
+            .unwrap_or_default();
+        table.add_row(vec![meal.to_string(), days.to_string(), most_common]);
+    }
+    table
+}
+
+/// Prints the events in the span.
+///
+/// If the span contains today, the events of the month around today are printed instead,
+/// as upcoming and recently completed.
+fn print_events(span: &DateSpan) {
+    let events_vec = read_events();
+    let today = journal_today();
+    if today < span.start || today > span.end {
+        println!("\n{}", "Events:".yellow().bold());
+        // Events are written without a year, so they happen in every year of the span
+        for year in span.start.year()..=span.end.year() {
+            for (date, event) in events_vec.iter() {
+                if let Some(date) = date.with_year(year)
+                    && date >= span.start
+                    && date <= span.end
+                {
+                    println!("[{}] {}", date.format("%Y-%m-%d").to_string().cyan(), event);
+                }
+            }
+        }
+        return;
+    }
+
+    let mut upcoming: Vec<String> = Vec::new();
+    let mut completed: Vec<String> = Vec::new();
+    for event in events_vec {
+        let diff = (event.0 - today).num_days();
+        if diff == 0 {
+            upcoming.push(format!(
+                "[{}] {}: {}",
+                event.0.format("%Y-%m-%d").to_string().cyan(),
+                "TODAY!".red().underline(),
+                event.1
+            ));
+        } else if diff == 1 {
+            upcoming.push(format!(
+                "[{}] {} day from now: {}",
+                event.0.format("%Y-%m-%d").to_string().cyan(),
+                diff.to_string().yellow(),
+                event.1
+            ));
+        } else if diff > 1 && diff <= 7 {
+            upcoming.push(format!(
+                "[{}] {} days from now: {}",
+                event.0.format("%Y-%m-%d").to_string().cyan(),
+                diff.to_string().yellow(),
+                event.1
+            ));
+        } else if diff > 8 && diff <= 30 {
+            upcoming.push(format!(
+                "[{}] {} days from now: {}",
+                event.0.format("%Y-%m-%d").to_string().cyan(),
+                diff.to_string().bold(),
+                event.1
+            ));
+        } else if diff == -1 {
+            completed.push(format!(
+                "[{}] {} day ago: {}",
+                event.0.format("%Y-%m-%d").to_string().cyan(),
+                (-diff).to_string().bold(),
+                event.1
+            ));
+        } else if (-30..-1).contains(&diff) {
+            completed.push(format!(
+                "[{}] {} days ago: {}",
+                event.0.format("%Y-%m-%d").to_string().cyan(),
+                (-diff).to_string().bold(),
+                event.1
+            ));
+        }
+    }
+
+    println!("\n{}", "Upcoming Events:".yellow().bold());
+    for item in upcoming {
+        println!("{}", item);
+    }
+    println!("\n{}", "Recently completed Events:".yellow().bold());
+    for item in completed {
+        println!("{}", item);
+    }
+}
+
+/// Generates a report for a span of dates: a month, a year, a quarter, a week or any range.
+///
+/// ## Sample output:
+/// Note that colors are present, but cannot be shown here.
+/// In the calendars, the dates when entries are present will be highlighted.
+///
+/// ```text
+/// Report for Q2 2026 (April to June)
+///
+/// Number of entries: 17
+/// April: 5
+/// May: 12
+/// June: 0
+///
+///       April 2026             May 2026               June 2026
+///  Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su
+///      ...
+///
+/// Most used tags:
+/// ╭───────────┬───────────╮
+/// │ Tag       ┆ Frequency │
+/// ╞═══════════╪═══════════╡
+/// │ work      ┆ 12        │
+/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
+/// │  └ projx  ┆ 8         │
+/// ╰───────────┴───────────╯
+///
+/// Food:
+/// ╭───────────┬──────────────┬─────────────╮
+/// │ Meal      ┆ Times logged ┆ Most common │
+/// ╞═══════════╪══════════════╪═════════════╡
+/// │ Breakfast ┆ 12           ┆ eggs (5)    │
+/// ╰───────────┴──────────────┴─────────────╯
+///
+/// Writing:
+/// Total words: 810
+/// Words per entry: 48
+/// Records per day: 4.2
+/// Longest entry: 2026-05-03 (310 words)
+/// Shortest entry: 2026-05-11 (4 words)
+/// Most written at: 22:00-23:00
+/// Most written on: Sun
+///
+/// Tasks: 2 open, 3 done, 1 carried over
+/// [2026-05-14] call the bank
+///
+/// Events:
+/// [2026-05-15] Team offsite
+/// ```
+///
+/// Hierarchical tags(`[work/projx]`) are rolled up under their parent, see [`make_tag_tree_table`].
+pub fn gen_report(span: &DateSpan) {
+    let data = collect_report(span);
+    // A journal file without entries still gets its(empty) report
+    if get_journal_files_between(span.start, span.end).is_empty() && span.kind != SpanKind::Week {
+        eprintln!(
+            "{}",
+            format!("There are no entries in {}", span_title(span).underline()).red()
+        );
+        process::exit(1);
+    }
+
+    println!(
+        "{}",
+        format!("Report for {}\n", span_title(span))
+            .bold()
+            .cyan()
+            .underline()
+    );
+
+    let today = journal_today();
+    if read_config().0.on_this_day_in_report && today >= span.start && today <= span.end {
+        let section = crate::onthisday::on_this_day_section(today);
+        if !section.is_empty() {
+            println!("{}", section);
+        }
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Number of entries: {}",
+            data.entries.len().to_string().bold()
+        )
+        .yellow()
+    );
+    let months = months_in_span(span);
+    if months.len() > 1 && span.kind != SpanKind::Week {
+        for (year, month) in months.iter() {
+            let count = data
+                .entries
+                .iter()
+                .filter(|date| date.year() == *year && date.month() == *month)
+                .count();
+            println!("{} {}: {}", month_no_to_name(*month), year, count);
+        }
+    }
+    println!("\n{}", span_calendar(span, &data.entries));
+
+    // Most used tags
+    println!("{}", "Most used tags:".yellow().bold());
+    println!("{}", make_tag_tree_table(&data.tags));
+
+    if !data.food.is_empty() {
+        println!("\n{}", "Food:".yellow().bold());
+        println!("{}", make_food_summary(&data.food));
+    }
+
+    println!("\n{}", "Writing:".yellow().bold());
+    print!("{}", crate::stats::writing_summary(&data));
+
+    let start = span.start.format("%Y-%m-%d").to_string();
+    let end = span.end.format("%Y-%m-%d").to_string();
+    let tasks: Vec<_> = get_all_tasks()
+        .into_iter()
+        .filter(|task| task.date >= start && task.date <= end)
+        .collect();
+    if !tasks.is_empty() {
+        let count = |state: char| tasks.iter().filter(|task| task.state == state).count();
+        println!(
+            "\n{} {} open, {} done, {} carried over",
+            "Tasks:".yellow().bold(),
+            count(' ').to_string().bold(),
+            count('x').to_string().bold(),
+            count('>').to_string().bold()
+        );
+        for task in tasks.iter().filter(|task| task.state == ' ') {
+            println!("[{}] {}", task.date.cyan(), task.text);
+        }
+    }
+
+    print_events(span);
+}
+
+/// Returns the change from `before` to `after`, as "+3 (+25%)", colored green if it went up
+/// and red if it went down.
+fn format_change(before: f64, after: f64) -> String {
+    let diff = after - before;
+    let percent = if before == 0.0 {
+        String::from("new")
+    } else {
+        format!("{:+.0}%", diff / before * 100.0)
+    };
+    let change = format!("{:+} ({})", diff.round(), percent);
+    if diff > 0.0 {
+        change.green().to_string()
+    } else if diff < 0.0 {
+        change.red().to_string()
+    } else {
+        change
+    }
+}
+
+/// Prints what changed from one span to the other: entries written, average entry length,
+/// and the frequency of each tag.
+///
+/// # Sample output:
+/// Note that the headings are colored in green
+///
+/// ```text
+/// Comparing September, 2026 with October, 2026
+///
+/// Entries written: 12 -> 15, +3 (+25%)
+/// Average entry length: 40 -> 52 words, +12 (+30%)
+///
+/// ╭───────┬─────────────────┬───────────────┬─────────────╮
+/// │ Tag   ┆ September, 2026 ┆ October, 2026 ┆ Change      │
+/// ╞═══════╪═════════════════╪═══════════════╪═════════════╡
+/// │ work  ┆ 10              ┆ 14            ┆ +4 (+40%)   │
+/// ├╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ gym   ┆ 0               ┆ 6             ┆ +6 (new)    │
+/// ├╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ chess ┆ 3               ┆ 0             ┆ -3 (-100%)  │
+/// ╰───────┴─────────────────┴───────────────┴─────────────╯
+///
+/// New tags: gym
+/// Disappeared tags: chess
+/// ```
+pub fn print_comparison(before: &DateSpan, after: &DateSpan) {
+    let (old, new) = (collect_report(before), collect_report(after));
+    let (old_title, new_title) = (span_title(before), span_title(after));
+    let average = |data: &ReportData| {
+        if data.entries.is_empty() {
+            0.0
+        } else {
+            (data.words as f64 / data.entries.len() as f64).round()
+        }
+    };
+
+    println!(
+        "{}",
+        format!("Comparing {} with {}\n", old_title, new_title)
+            .bold()
+            .cyan()
+            .underline()
+    );
+    println!(
+        "{} {} -> {}, {}",
+        "Entries written:".yellow(),
+        old.entries.len(),
+        new.entries.len().to_string().bold(),
+        format_change(old.entries.len() as f64, new.entries.len() as f64)
+    );
+    println!(
+        "{} {:.0} -> {} words, {}\n",
+        "Average entry length:".yellow(),
+        average(&old),
+        format!("{:.0}", average(&new)).bold(),
+        format_change(average(&old), average(&new))
+    );
+
+    // Most used(in either span) first
+    let mut tags: Vec<&String> = old.tags.keys().chain(new.tags.keys()).collect();
+    tags.sort();
+    tags.dedup();
+    let count = |data: &ReportData, tag: &String| *data.tags.get(tag).unwrap_or(&0);
+    tags.sort_by_key(|tag| std::cmp::Reverse(count(&old, tag).max(count(&new, tag))));
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Tag".green(),
+            old_title.green(),
+            new_title.green(),
+            "Change".green(),
+        ]);
+    for tag in tags.iter() {
+        let (before, after) = (count(&old, tag), count(&new, tag));
+        table.add_row(vec![
+            tag.cyan().to_string(),
+            before.to_string(),
+            after.to_string(),
+            format_change(before as f64, after as f64),
+        ]);
+    }
+    let mut output = format!("{}\n", table);
+
+    let appeared: Vec<&str> = tags
+        .iter()
+        .filter(|tag| !old.tags.contains_key(**tag))
+        .map(|tag| tag.as_str())
+        .collect();
+    let disappeared: Vec<&str> = tags
+        .iter()
+        .filter(|tag| !new.tags.contains_key(**tag))
+        .map(|tag| tag.as_str())
+        .collect();
+    output.push_str(&format!(
+        "\n{} {}\n{} {}\n",
+        "New tags:".yellow().bold(),
+        appeared.join(", ").green(),
+        "Disappeared tags:".yellow().bold(),
+        disappeared.join(", ").red()
+    ));
+    print_with_pager(&output, tags.len());
+}
@@ -0,0 +1,259 @@
+//! Contains the writing statistics: `jrnl stats writing`, and the writing section of
+//! `--gen-report`.
+//!
+//! These look at the text itself(words and records), and at the writing times from the
+//! `### DAY (HH:MM:SS)` headers written when `add_timestamp` is on.
+use crate::dates::{DateSpan, config_week_start};
+use crate::funcs::*;
+use crate::report::{ReportData, collect_report, span_title};
+use crate::utils::*;
+use chrono::{Datelike, NaiveDate, Timelike};
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use std::process;
+
+/// Width of the longest bar in the histograms
+const BAR_WIDTH: usize = 30;
+
+/// Returns a bar for the histograms, as long as `count` is compared to `max`.
+fn bar(count: usize, max: usize) -> String {
+    if count == 0 || max == 0 {
+        return String::new();
+    }
+    "█".repeat((count * BAR_WIDTH).div_ceil(max))
+}
+
+/// Returns the summary of the writing: total words, words per entry, records per day,
+/// the longest and shortest entries, and when most of the writing happens.
+///
+/// # Sample output:
+/// ```text
+/// Total words: 5120
+/// Words per entry: 48
+/// Records per day: 4.2
+/// Longest entry: 2026-10-03 (310 words)
+/// Shortest entry: 2026-10-11 (4 words)
+/// Most written at: 22:00-23:00
+/// Most written on: Sun
+/// ```
+pub fn writing_summary(data: &ReportData) -> String {
+    let stats = &data.entry_stats;
+    if stats.is_empty() {
+        return String::from("No entries.\n");
+    }
+    let total_records: usize = stats.iter().map(|entry| entry.records).sum();
+    let mut output = format!(
+        "Total words: {}\nWords per entry: {}\nRecords per day: {:.1}\n",
+        data.words.to_string().bold(),
+        (data.words as f64 / stats.len() as f64).round(),
+        total_records as f64 / stats.len() as f64
+    );
+
+    // First of the longest, and first of the shortest
+    let longest = stats.iter().rev().max_by_key(|entry| entry.words).unwrap();
+    let shortest = stats.iter().min_by_key(|entry| entry.words).unwrap();
+    output.push_str(&format!(
+        "Longest entry: {} ({} words)\nShortest entry: {} ({} words)\n",
+        longest.date.format("%Y-%m-%d").to_string().cyan(),
+        longest.words,
+        shortest.date.format("%Y-%m-%d").to_string().cyan(),
+        shortest.words
+    ));
+
+    let hours = hour_counts(data);
+    if let Some(hour) = (0..24).rev().max_by_key(|hour| hours[*hour])
+        && hours[hour] > 0
+    {
+        output.push_str(&format!(
+            "Most written at: {:02}:00-{:02}:00\n",
+            hour,
+            (hour + 1) % 24
+        ));
+    }
+    let weekdays = weekday_counts(data);
+    if let Some((weekday, _)) = weekdays.iter().rev().max_by_key(|(_, count)| *count) {
+        output.push_str(&format!("Most written on: {}\n", weekday));
+    }
+    output
+}
+
+/// Returns the number of entries written in each hour of the day.
+fn hour_counts(data: &ReportData) -> [usize; 24] {
+    let mut hours = [0; 24];
+    for time in data.entry_stats.iter().filter_map(|entry| entry.time) {
+        hours[time.hour() as usize] += 1;
+    }
+    hours
+}
+
+/// Returns the number of entries on each weekday, starting from `week_start`.
+fn weekday_counts(data: &ReportData) -> Vec<(String, usize)> {
+    let mut weekday = config_week_start();
+    let mut output: Vec<(String, usize)> = Vec::new();
+    for _ in 0..7 {
+        let count = data
+            .entry_stats
+            .iter()
+            .filter(|entry| entry.date.weekday() == weekday)
+            .count();
+        output.push((format!("{:?}", weekday), count));
+        weekday = weekday.succ();
+    }
+    output
+}
+
+/// Makes the table of the words written in each month, or in each year if the span
+/// covers more than one year.
+///
+/// # Sample output:
+/// Note that the headings are colored in green
+///
+/// ```text
+/// ╭────────────────┬─────────┬───────┬─────────────────╮
+/// │ Month          ┆ Entries ┆ Words ┆ Words per entry │
+/// ╞════════════════╪═════════╪═══════╪═════════════════╡
+/// │ September 2026 ┆ 28      ┆ 1302  ┆ 47              │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ October 2026   ┆ 17      ┆ 918   ┆ 54              │
+/// ╰────────────────┴─────────┴───────┴─────────────────╯
+/// ```
+fn make_words_table(data: &ReportData, by_year: bool) -> Table {
+    // (period, entries, words)
+    let mut rows: Vec<(String, usize, usize)> = Vec::new();
+    for entry in data.entry_stats.iter() {
+        let period = if by_year {
+            entry.date.year().to_string()
+        } else {
+            format!(
+                "{} {}",
+                month_no_to_name(entry.date.month()),
+                entry.date.year()
+            )
+        };
+        match rows.last_mut() {
+            Some(row) if row.0 == period => {
+                row.1 += 1;
+                row.2 += entry.words;
+            }
+            _ => rows.push((period, 1, entry.words)),
+        }
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            if by_year { "Year" } else { "Month" }.green(),
+            "Entries".green(),
+            "Words".green(),
+            "Words per entry".green(),
+        ]);
+    for (period, entries, words) in rows {
+        table.add_row(vec![
+            period,
+            entries.to_string(),
+            words.to_string(),
+            (words as f64 / entries as f64).round().to_string(),
+        ]);
+    }
+    table
+}
+
+/// Prints all the writing statistics for the span, or for the whole journal if `None`.
+///
+/// # Sample output:
+/// ```text
+/// Writing statistics for 2026-01-03 to 2026-10-18
+///
+/// Total words: 5120
+/// ...(see `writing_summary`)
+///
+/// ╭──────────────┬─────────┬───────┬─────────────────╮
+/// │ Month        ┆ Entries ┆ Words ┆ Words per entry │
+/// ...(see `make_words_table`)
+///
+/// Writing times:
+/// 08:00 ███████ 4
+/// 09:00 ██ 1
+/// ...
+/// 22:00 ██████████████████████████████ 17
+///
+/// Weekdays:
+/// Mon ████████████ 6
+/// ...
+/// ```
+pub fn print_writing_stats(span: Option<DateSpan>) {
+    let span = match span {
+        Some(span) => span,
+        None => {
+            let entries = get_all_entries();
+            let date = |entry: Option<&(String, String)>| {
+                NaiveDate::parse_from_str(&entry?.0, "%Y-%m-%d").ok()
+            };
+            match (date(entries.first()), date(entries.last())) {
+                (Some(start), Some(end)) => DateSpan::range(start, end),
+                _ => {
+                    eprintln!("{}", "There are no entries in your journal.".red());
+                    process::exit(1);
+                }
+            }
+        }
+    };
+    let data = collect_report(&span);
+    if data.entry_stats.is_empty() {
+        eprintln!(
+            "{}",
+            format!("There are no entries in {}", span_title(&span).underline()).red()
+        );
+        process::exit(1);
+    }
+
+    let mut output = format!(
+        "{}\n\n{}\n",
+        format!("Writing statistics for {}", span_title(&span))
+            .bold()
+            .cyan()
+            .underline(),
+        writing_summary(&data)
+    );
+    output.push_str(&format!(
+        "{}\n",
+        make_words_table(&data, span.start.year() != span.end.year())
+    ));
+
+    output.push_str(&format!("\n{}\n", "Writing times:".yellow().bold()));
+    let hours = hour_counts(&data);
+    let max = *hours.iter().max().unwrap();
+    if max == 0 {
+        output.push_str(
+            "No timestamps found. Turn on `add_timestamp` in the config to track them.\n",
+        );
+    } else {
+        // Only from the first to the last hour written in
+        let first = hours.iter().position(|count| *count > 0).unwrap();
+        let last = hours.iter().rposition(|count| *count > 0).unwrap();
+        for (hour, count) in hours.iter().enumerate().take(last + 1).skip(first) {
+            output.push_str(&format!(
+                "{:02}:00 {} {}\n",
+                hour,
+                bar(*count, max).green(),
+                count
+            ));
+        }
+    }
+
+    output.push_str(&format!("\n{}\n", "Weekdays:".yellow().bold()));
+    let weekdays = weekday_counts(&data);
+    let max = weekdays.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    for (weekday, count) in weekdays {
+        output.push_str(&format!(
+            "{} {} {}\n",
+            weekday,
+            bar(count, max).green(),
+            count
+        ));
+    }
+    print_with_pager(&output, output.lines().count());
+}
@@ -0,0 +1,374 @@
+//! Contains `jrnl sync`, which merges this journal with another copy of it(on a USB drive,
+//! a shared folder, etc), entry by entry.
+//!
+//! The state of both journals after each sync is kept in `jrnl_folder/.sync/` of both, as
+//! the base of the next one. For each date, an entry changed on only one side since then is
+//! copied to the other, and an entry changed on both is written on both with conflict
+//! markers, to be fixed by hand:
+//! ```text
+//! ### THURSDAY
+//! # 2026-10-01
+//! <<<<<<< this journal
+//! - [work/projx] kickoff
+//! =======
+//! - [work/projx] kickoff, moved to friday
+//! >>>>>>> other journal
+//! ```
+//! Attachments missing on one side are copied too. Notes are not synced.
+//!
+//! Each journal is read and written in its own layout, which is kept in its
+//! `jrnl_folder/.sync/layout`, so the two can be in different layouts.
+use crate::attachments::{copy_attachments, get_attachment_files_in};
+use crate::backup::backup_before_rewrite;
+use crate::dates::journal_now;
+use crate::funcs::*;
+use crate::get_default_path;
+use crate::layout::*;
+use crate::lock::*;
+use chrono::NaiveDate;
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+    process,
+};
+
+/// The state of a journal at the last sync: the text of each date's entries.
+type Base = BTreeMap<String, String>;
+
+/// A layout with every file at the top of `jrnl_folder`. The entries are compared(and kept
+/// in the base) with their links to attachments as in it, so that journals in different
+/// layouts can be compared.
+const FLAT: &str = "{YYYY}-{MM}-{DD}.md";
+
+/// Returns the id of the journal in the folder(a `jrnl_folder`), making it on the first sync.
+/// The bases are kept by the id of the other journal, as its path can change(like the
+/// mount point of a USB drive).
+fn journal_id(root: &str) -> String {
+    let id_file = format!("{}/.sync/id", root);
+    if let Ok(id) = fs::read_to_string(&id_file)
+        && !id.trim().is_empty()
+    {
+        return id.trim().to_string();
+    }
+    let seed = format!("{} {} {}", root, journal_now(), process::id());
+    let id = format!("{:x}", Sha256::digest(seed.as_bytes()))[..16].to_string();
+    if let Err(e) = fs::create_dir_all(format!("{}/.sync", root)) {
+        panic!("An error occured: {}", e);
+    }
+    if let Err(e) = write_atomic(&id_file, format!("{}\n", id)) {
+        panic!("An error occured: {}", e);
+    }
+    id
+}
+
+/// Returns the base kept in the folder(a `jrnl_folder`) for the other journal, if any.
+fn read_base(root: &str, other_id: &str) -> Option<Base> {
+    let contents = fs::read_to_string(format!("{}/.sync/{}.json", root, other_id)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Keeps the base in the folder(a `jrnl_folder`) for the other journal.
+fn write_base(root: &str, other_id: &str, base: &Base) {
+    let contents = match serde_json::to_string_pretty(base) {
+        Ok(data) => data,
+        Err(e) => panic!("An error occured: {}", e),
+    };
+    if let Err(e) = fs::create_dir_all(format!("{}/.sync", root)) {
+        panic!("An error occured: {}", e);
+    }
+    if let Err(e) = write_atomic(&format!("{}/.sync/{}.json", root, other_id), contents) {
+        panic!("An error occured: {}", e);
+    }
+}
+
+/// Returns the text of a date's entries, as compared between the journals.
+fn entries_text(entries: &Entries, date: &NaiveDate) -> Option<String> {
+    let text = entries
+        .get(date)?
+        .iter()
+        .map(|lines| lines.join("\n"))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    Some(text)
+}
+
+/// Returns the entry with conflict markers around the records of both journals.
+/// The heading is kept outside of them, so that it is still read as the date's entry.
+fn conflict_entry(date: &str, this: Option<&str>, other: Option<&str>) -> String {
+    let is_heading = |line: &&str| line.starts_with("# ") || line.starts_with("### ");
+    let heading: Vec<&str> = this
+        .or(other)
+        .unwrap_or_default()
+        .lines()
+        .take_while(is_heading)
+        .collect();
+    let records = |text: Option<&str>, side: &str| match text {
+        Some(text) => text
+            .lines()
+            .filter(|line| !is_heading(line) && !line.trim().is_empty())
+            .collect::<Vec<&str>>()
+            .join("\n"),
+        None => format!("(deleted in {})", side),
+    };
+    let heading = if heading.is_empty() {
+        format!("# {}", date)
+    } else {
+        heading.join("\n")
+    };
+    format!(
+        "{}\n<<<<<<< this journal\n{}\n=======\n{}\n>>>>>>> other journal",
+        heading,
+        records(this, "this journal"),
+        records(other, "the other journal")
+    )
+}
+
+/// Merges this journal and the other one(a `jrnl_folder`, or the folder containing it),
+/// entry by entry, after showing what will be done and asking for confirmation.
+///
+/// # Sample output:
+/// ```text
+/// ╭────────────┬───────────────────────────╮
+/// │ Date       ┆ Change                    │
+/// ╞════════════╪═══════════════════════════╡
+/// │ 2026-10-01 ┆ conflict                  │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ 2026-10-17 ┆ from the other journal    │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ 2026-10-18 ┆ to the other journal      │
+/// ╰────────────┴───────────────────────────╯
+/// ```
+pub fn sync(other: &str) {
+    let root = format!("{}/jrnl_folder", get_default_path());
+    let other_root = resolve_jrnl_folder(other);
+    if !Path::new(&root).is_dir() {
+        eprintln!(
+            "{}",
+            "There doesn't seem to be a `jrnl_folder`. Please create it.".red()
+        );
+        process::exit(1);
+    }
+    if fs::canonicalize(&root).ok() == fs::canonicalize(&other_root).ok() {
+        eprintln!("{}", "Both journals are the same folder.".red());
+        process::exit(1);
+    }
+
+    let this_pattern = config_layout();
+    let other_pattern = folder_layout(&other_root);
+    if folder_layout(&root) != this_pattern {
+        eprintln!(
+            "{}",
+            format!(
+                "This journal was in the layout {} at the last sync, but `layout` is now {}.",
+                folder_layout(&root),
+                this_pattern
+            )
+            .red()
+        );
+        eprintln!(
+            "{}: Use `jrnl migrate` to change the layout. If the files were moved by hand, put the new pattern in {}/.sync/layout.",
+            "HELP".green().bold(),
+            root
+        );
+        process::exit(1);
+    }
+    let this_id = journal_id(&root);
+    let other_id = journal_id(&other_root);
+    let base = read_base(&root, &other_id).or_else(|| read_base(&other_root, &this_id));
+    let first_sync = base.is_none();
+    let base: Base = base.unwrap_or_default();
+    // A journal read as empty would have every entry deleted from the other one. Only a
+    // new copy(an empty folder, never synced) can be empty.
+    for (folder, pattern) in [(&root, &this_pattern), (&other_root, &other_pattern)] {
+        if get_journal_files_in(folder, pattern).is_empty()
+            && (!base.is_empty() || !is_empty_folder(folder))
+        {
+            eprintln!(
+                "{}",
+                format!(
+                    "There are no journal files in the layout {} in {}. Nothing was changed.",
+                    pattern, folder
+                )
+                .red()
+            );
+            eprintln!(
+                "{}: If it is in another layout, put its pattern in {}/.sync/layout.",
+                "HELP".green().bold(),
+                folder
+            );
+            process::exit(1);
+        }
+    }
+    // As read, to check that they are unchanged when writing
+    let this_read = read_entries_in(&root, &this_pattern);
+    let other_read = read_entries_in(&other_root, &other_pattern);
+    let mut this_entries = relink_entries(&this_read, &this_pattern, FLAT);
+    let mut other_entries = relink_entries(&other_read, &other_pattern, FLAT);
+
+    let mut dates: BTreeSet<NaiveDate> = BTreeSet::new();
+    dates.extend(this_entries.keys());
+    dates.extend(other_entries.keys());
+    // (date, change)
+    let mut changes: Vec<(NaiveDate, &str)> = Vec::new();
+    let mut to_this: Vec<NaiveDate> = Vec::new();
+    let mut to_other: Vec<NaiveDate> = Vec::new();
+    for date in dates {
+        let date_str = date.format("%Y-%m-%d").to_string();
+        let this = entries_text(&this_entries, &date);
+        let other = entries_text(&other_entries, &date);
+        let before = base.get(&date_str).cloned();
+        if this == other {
+            continue;
+        }
+        if this == before {
+            match other_entries.get(&date) {
+                Some(entries) => this_entries.insert(date, entries.clone()),
+                None => this_entries.remove(&date),
+            };
+            to_this.push(date);
+            changes.push((date, "from the other journal"));
+        } else if other == before {
+            match this_entries.get(&date) {
+                Some(entries) => other_entries.insert(date, entries.clone()),
+                None => other_entries.remove(&date),
+            };
+            to_other.push(date);
+            changes.push((date, "to the other journal"));
+        } else {
+            let entry = conflict_entry(&date_str, this.as_deref(), other.as_deref());
+            let lines: Vec<String> = entry.lines().map(|line| line.to_string()).collect();
+            this_entries.insert(date, vec![lines.clone()]);
+            other_entries.insert(date, vec![lines]);
+            to_this.push(date);
+            to_other.push(date);
+            changes.push((date, "conflict"));
+        }
+    }
+
+    // Attachments are never changed, only added, so the missing ones are copied
+    let this_attachments = get_attachment_files_in(&root);
+    let other_attachments = get_attachment_files_in(&other_root);
+    let attachments_to_this: Vec<String> = other_attachments
+        .iter()
+        .filter(|a| !this_attachments.contains(a))
+        .cloned()
+        .collect();
+    let attachments_to_other: Vec<String> = this_attachments
+        .iter()
+        .filter(|a| !other_attachments.contains(a))
+        .cloned()
+        .collect();
+
+    if changes.is_empty() && attachments_to_this.is_empty() && attachments_to_other.is_empty() {
+        println!("{}", "Both journals are already the same.".green());
+        let new_base = make_base(&this_entries);
+        write_base(&root, &other_id, &new_base);
+        write_base(&other_root, &this_id, &new_base);
+        write_folder_layout(&root, &this_pattern);
+        write_folder_layout(&other_root, &other_pattern);
+        return;
+    }
+    if first_sync {
+        println!(
+            "{}: This is the first sync with {}, so entries which differ are conflicts.",
+            "NOTE".yellow().bold(),
+            other_root.underline()
+        );
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Date".green(), "Change".green()]);
+    for (date, change) in changes.iter() {
+        let change = if *change == "conflict" {
+            change.red().to_string()
+        } else {
+            change.to_string()
+        };
+        table.add_row(vec![date.format("%Y-%m-%d").to_string(), change]);
+    }
+    if !changes.is_empty() {
+        println!("{}", table);
+    }
+    println!(
+        "{} entries to this journal, {} to the other, {} conflict(s), and {} attachment(s) to copy.",
+        to_this.len().to_string().bold(),
+        to_other.len().to_string().bold(),
+        changes
+            .iter()
+            .filter(|(_, change)| *change == "conflict")
+            .count()
+            .to_string()
+            .bold(),
+        (attachments_to_this.len() + attachments_to_other.len())
+            .to_string()
+            .bold()
+    );
+    if !inquire_confirm("Sync the journals?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Held until everything is written, so that no change made meanwhile is lost
+    let _lock = lock_journal();
+    let _other_lock = lock_other_journal(&other_root);
+    if read_entries_in(&root, &this_pattern) != this_read
+        || read_entries_in(&other_root, &other_pattern) != other_read
+    {
+        eprintln!(
+            "{}",
+            "A journal was changed by another jrnl meanwhile. Nothing was changed; try again."
+                .red()
+        );
+        process::exit(1);
+    }
+    backup_before_rewrite();
+
+    // Each in its own layout
+    let this_files = relink_entries(&this_entries, FLAT, &this_pattern);
+    let other_files = relink_entries(&other_entries, FLAT, &other_pattern);
+    write_entries_in(&root, &this_pattern, &this_files, &to_this);
+    write_entries_in(&other_root, &other_pattern, &other_files, &to_other);
+    copy_attachments(&other_root, &root, &attachments_to_this);
+    copy_attachments(&root, &other_root, &attachments_to_other);
+    // Both journals now have the same entries
+    let new_base = make_base(&this_entries);
+    write_base(&root, &other_id, &new_base);
+    write_base(&other_root, &this_id, &new_base);
+    write_folder_layout(&root, &this_pattern);
+    write_folder_layout(&other_root, &other_pattern);
+
+    let conflicts: Vec<&NaiveDate> = changes
+        .iter()
+        .filter(|(_, change)| *change == "conflict")
+        .map(|(date, _)| date)
+        .collect();
+    if conflicts.is_empty() {
+        println!("{}", "Done.".green());
+        return;
+    }
+    eprintln!(
+        "{}: These entries were changed in both journals, and now have conflict markers(<<<<<<<, =======, >>>>>>>):",
+        "CONFLICT".yellow().bold()
+    );
+    for date in conflicts {
+        eprintln!("  {}", date.format("%Y-%m-%d").to_string().cyan());
+    }
+    eprintln!("Fix them in either journal, and sync again.");
+}
+
+/// Returns the base for the next sync, from the entries both journals have.
+fn make_base(entries: &Entries) -> Base {
+    entries
+        .keys()
+        .filter_map(|date| {
+            let text = entries_text(entries, date)?;
+            Some((date.format("%Y-%m-%d").to_string(), text))
+        })
+        .collect()
+}
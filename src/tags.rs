@@ -0,0 +1,283 @@
+//! Contains the functions for `jrnl tags`, which work on the tags across the whole journal.
+//!
+//! Unlike `-t`, which looks at a month or a year, these go over every journal file in `jrnl_folder`.
+use crate::backup::backup_before_rewrite;
+use crate::funcs::*;
+use crate::layout::get_journal_files;
+use crate::lock::*;
+use crate::utils::*;
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use std::{collections::HashMap, fs, process};
+
+/// How often, and when, a tag has been used.
+#[derive(Debug, Clone)]
+pub struct TagUsage {
+    /// The tag, without the brackets
+    pub tag: String,
+
+    /// Number of times the tag has been used
+    pub count: u32,
+
+    /// Date(YYYY-MM-DD) of the first entry using the tag
+    pub first: String,
+
+    /// Date(YYYY-MM-DD) of the last entry using the tag
+    pub last: String,
+}
+
+/// Returns every tag used in the journal, along with the date of the entry it is in,
+/// as (date, tag).
+pub fn get_all_tags() -> Vec<(String, String)> {
+    let mut output: Vec<(String, String)> = Vec::new();
+    for (_, _, filename) in get_journal_files() {
+        let contents = fs::read_to_string(&filename).unwrap_or_default();
+        let mut entry_date = String::new();
+        for line in contents.lines() {
+            if line.starts_with("### ") {
+                continue;
+            }
+            if line.starts_with("# ") {
+                entry_date = line[1..].trim().to_string();
+                continue;
+            }
+            for tag in get_tags_from_line(line) {
+                output.push((entry_date.clone(), tag));
+            }
+        }
+    }
+    output
+}
+
+/// Returns the usage of all tags in the journal, sorted by `sort`,
+/// which is one of "name", "count", "first" or "last".
+pub fn get_tag_usage(sort: &str) -> Vec<TagUsage> {
+    let mut usage_map: HashMap<String, TagUsage> = HashMap::new();
+    for (date, tag) in get_all_tags() {
+        let usage = usage_map.entry(tag.clone()).or_insert(TagUsage {
+            tag,
+            count: 0,
+            first: date.clone(),
+            last: date.clone(),
+        });
+        usage.count += 1;
+        if date < usage.first {
+            usage.first = date.clone();
+        }
+        if date > usage.last {
+            usage.last = date;
+        }
+    }
+
+    let mut usage: Vec<TagUsage> = usage_map.into_values().collect();
+    usage.sort_by(|a, b| a.tag.cmp(&b.tag));
+    match sort {
+        "name" => (),
+        "first" => usage.sort_by(|a, b| a.first.cmp(&b.first)),
+        "last" => usage.sort_by(|a, b| b.last.cmp(&a.last)),
+        _ => usage.sort_by_key(|a| std::cmp::Reverse(a.count)),
+    }
+    usage
+}
+
+/// Prints a table of all tags ever used, with their count and first and last use.
+///
+/// # Sample output:
+/// Note that the headings are colored in green.
+///
+/// ```text
+/// ╭───────┬───────┬────────────┬────────────╮
+/// │ Tag   ┆ Count ┆ First used ┆ Last used  │
+/// ╞═══════╪═══════╪════════════╪════════════╡
+/// │ food  ┆ 31    ┆ 2025-03-01 ┆ 2025-04-30 │
+/// ├╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ work  ┆ 12    ┆ 2025-03-04 ┆ 2025-04-28 │
+/// ╰───────┴───────┴────────────┴────────────╯
+/// ```
+pub fn print_tag_inventory(sort: &str) {
+    let usage = get_tag_usage(sort);
+    if usage.is_empty() {
+        println!("No tags found in your journal.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Tag".green(),
+            "Count".green(),
+            "First used".green(),
+            "Last used".green(),
+        ]);
+    for item in usage.iter() {
+        table.add_row(vec![
+            item.tag.cyan().to_string(),
+            item.count.to_string(),
+            item.first.clone(),
+            item.last.clone(),
+        ]);
+    }
+    print_with_pager(&format!("{}", table), usage.len());
+}
+
+/// Replaces the tags `from`(and their subtags) with the tag `into` in a single line.
+///
+/// Only the tags found by [`get_tags_from_line`] are changed, each of them once, so task
+/// checkboxes(`- [x]`), wiki links(`[[events]]`) and the labels of Markdown links are left
+/// alone. If the rename makes the same tag more than once in the line(when merging tags),
+/// only the first is kept.
+///
+/// ## Example:
+/// ```
+/// let from = vec![String::from("work")];
+/// let line = replace_tags_in_line("- [work] [work/projx] standup", &from, "work/general");
+/// assert_eq!(line, "- [work/general] [work/general/projx] standup");
+/// ```
+pub fn replace_tags_in_line(line: &str, from: &[String], into: &str) -> String {
+    let mut new_line = String::new();
+    let mut renamed: Vec<String> = Vec::new();
+    // Where the part of the line not copied yet starts
+    let mut copied = 0;
+    for (start, end) in get_tag_spans(line) {
+        let tag = &line[start..end];
+        let Some(new_tag) = from.iter().find_map(|f| rename_tag(tag, f, into)) else {
+            continue;
+        };
+        let is_bracketed = line[..start].ends_with('[') && line[end..].starts_with(']');
+        if renamed.contains(&new_tag) && is_bracketed && start > copied {
+            // Removed, along with the space before it
+            let mut tag_start = start - 1;
+            if line[copied..tag_start].ends_with(' ') {
+                tag_start -= 1;
+            }
+            new_line.push_str(&line[copied..tag_start]);
+            copied = end + 1;
+            continue;
+        }
+        new_line.push_str(&line[copied..start]);
+        new_line.push_str(&new_tag);
+        copied = end;
+        renamed.push(new_tag);
+    }
+    new_line.push_str(&line[copied..]);
+    new_line
+}
+
+/// Returns the new name of the tag, if it is `from` or one of its subtags(`from/projx`
+/// becomes `into/projx`), so that the hierarchy is kept.
+fn rename_tag(tag: &str, from: &str, into: &str) -> Option<String> {
+    if tag == from {
+        return Some(into.to_string());
+    }
+    let rest = tag.strip_prefix(from)?.strip_prefix('/')?;
+    Some(format!("{}/{}", into, rest))
+}
+
+/// Renames(or merges) the tags `from` into the tag `into`, in every journal file.
+///
+/// A preview of all the changed lines is shown first, and nothing is written
+/// unless the user confirms it.
+pub fn rewrite_tags(from: &[String], into: &str) {
+    // (filename, contents), to check that they are unchanged when writing
+    let mut read_files: Vec<(String, String)> = Vec::new();
+    // (filename, new contents)
+    let mut changed_files: Vec<(String, String)> = Vec::new();
+    let mut no_of_changes = 0;
+
+    for (_, _, filename) in get_journal_files() {
+        let contents = fs::read_to_string(&filename).unwrap_or_default();
+        read_files.push((filename.clone(), contents.clone()));
+        let mut lines: Vec<String> = Vec::new();
+        let mut file_changed = false;
+        for (i, line) in contents.split('\n').enumerate() {
+            let new_line = if line.starts_with('#') {
+                line.to_string()
+            } else {
+                replace_tags_in_line(line, from, into)
+            };
+            if new_line != line {
+                if !file_changed {
+                    println!("{}", filename.underline().bold());
+                    file_changed = true;
+                }
+                println!("{:>5} {} {}", i + 1, "-".red(), line.red());
+                println!("{:>5} {} {}", i + 1, "+".green(), new_line.green());
+                no_of_changes += 1;
+            }
+            lines.push(new_line);
+        }
+        if file_changed {
+            changed_files.push((filename, lines.join("\n")));
+        }
+    }
+
+    if no_of_changes == 0 {
+        println!(
+            "No records found with the tag(s): {}",
+            from.join(", ").cyan()
+        );
+        process::exit(1);
+    }
+    println!(
+        "\n{} line(s) will be changed in {} file(s).",
+        no_of_changes.to_string().bold(),
+        changed_files.len().to_string().bold()
+    );
+    if !inquire_confirm("Apply these changes?") {
+        println!("{}", "Nothing was changed.".yellow());
+        return;
+    }
+    // Held until everything is written, so that no change made meanwhile is lost
+    let _lock = lock_unchanged(&read_files);
+    backup_before_rewrite();
+    for (filename, contents) in changed_files {
+        match write_journal(&filename, &contents) {
+            Ok(_) => (),
+            Err(e) => panic!("An error occured: {}", e),
+        }
+    }
+    println!("{}", "Done.".green());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(line: &str, from: &[&str], into: &str) -> String {
+        let from: Vec<String> = from.iter().map(|tag| tag.to_string()).collect();
+        replace_tags_in_line(line, &from, into)
+    }
+
+    #[test]
+    fn rename_into_own_subtag() {
+        assert_eq!(
+            rename("- [work] [work/projx] standup", &["work"], "work/general"),
+            "- [work/general] [work/general/projx] standup"
+        );
+        assert_eq!(
+            rename("- [workout] run", &["work"], "work/general"),
+            "- [workout] run"
+        );
+    }
+
+    #[test]
+    fn rename_leaves_checkboxes_and_links() {
+        assert_eq!(rename("- [x] paid rent", &["x"], "done"), "- [x] paid rent");
+        assert_eq!(
+            rename("- [x] [x] see [x](x.md) and [[x]]", &["x"], "done"),
+            "- [x] [done] see [x](x.md) and [[x]]"
+        );
+    }
+
+    #[test]
+    fn rename_dedupes_only_renamed_tags() {
+        assert_eq!(rename("- [a] [b] call", &["a", "b"], "c"), "- [c] call");
+        assert_eq!(
+            rename("- [c] [c] [d] call", &["d"], "e"),
+            "- [c] [c] [e] call"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Contains the timer: `jrnl start`, `jrnl stop` and `jrnl status`.
+//!
+//! The running timer is kept in `jrnl_folder/.timer`, as two lines: when it was started
+//! (YYYY-MM-DD HH:MM:SS, in the configured `timezone`) and the record.
+//! When stopped, the record is added to the entry with its time range, to be picked up
+//! by `jrnl timesheet`:
+//! ```text
+//! - 14:02-15:10 [work] code review
+//! ```
+use crate::dates::{config_day_start, journal_date, journal_now};
+use crate::get_default_path;
+use crate::lock::{lock_journal, write_journal};
+use crate::timesheet::format_minutes;
+use crate::utils::*;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use colored::Colorize;
+use std::{fs, io::ErrorKind, process};
+
+/// Returns the path of the file holding the running timer.
+fn timer_file() -> String {
+    format!("{}/jrnl_folder/.timer", get_default_path())
+}
+
+/// Returns the running timer as (when it was started, record), if there is one.
+pub fn read_timer() -> Option<(NaiveDateTime, String)> {
+    let contents = match fs::read_to_string(timer_file()) {
+        Ok(data) => data,
+        Err(e) => match e.kind() {
+            ErrorKind::NotFound => return None,
+            other => panic!("Error reading file: {other}"),
+        },
+    };
+    let (started, record) = contents.split_once('\n')?;
+    let started = NaiveDateTime::parse_from_str(started.trim(), "%Y-%m-%d %H:%M:%S").ok()?;
+    Some((started, record.trim().to_string()))
+}
+
+/// Starts a timer for the record, unless one is already running.
+pub fn start_timer(record: &str) {
+    // Held until it is written, so that two timers are never started at once
+    let _lock = lock_journal();
+    if let Some((started, running)) = read_timer() {
+        eprintln!(
+            "{}",
+            format!(
+                "A timer is already running since {}: {}",
+                started.format("%H:%M"),
+                running
+            )
+            .red()
+        );
+        eprintln!("Use `jrnl stop` to stop it first.");
+        process::exit(1);
+    }
+    let now = journal_now();
+    let contents = format!("{}\n{}\n", now.format("%Y-%m-%d %H:%M:%S"), record.trim());
+    match write_journal(&timer_file(), &contents) {
+        Ok(_) => (),
+        Err(e) => match e.kind() {
+            ErrorKind::NotFound => {
+                eprintln!(
+                    "{}",
+                    "There doesn't seem to be a `jrnl_folder`. Please create it.".red()
+                );
+                process::exit(1);
+            }
+            other => panic!("An error occured: {other}"),
+        },
+    }
+    println!(
+        "{} {} at {}",
+        "Started:".green().bold(),
+        record.trim(),
+        now.format("%H:%M").to_string().cyan()
+    );
+}
+
+/// Splits the time from `start` to `end` into the journal days it covers, as
+/// (date, start, end). A new journal day begins at `day_starts_at`.
+fn split_by_day(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Vec<(NaiveDate, NaiveDateTime, NaiveDateTime)> {
+    let mut output: Vec<(NaiveDate, NaiveDateTime, NaiveDateTime)> = Vec::new();
+    let mut day = journal_date(start);
+    let mut segment_start = start;
+    loop {
+        let next_day = (day + Duration::days(1)).and_time(config_day_start());
+        if end <= next_day {
+            output.push((day, segment_start, end));
+            return output;
+        }
+        output.push((day, segment_start, next_day));
+        segment_start = next_day;
+        day += Duration::days(1);
+    }
+}
+
+/// Stops the running timer, and adds its record to the entry(s) with the time range.
+///
+/// If the timer ran past the start of a new day, the time is split between the entries
+/// of both days.
+pub fn stop_timer() {
+    // Held until the timer is removed, so that its record is never added twice
+    let _lock = lock_journal();
+    let (started, record) = match read_timer() {
+        Some(timer) => timer,
+        None => {
+            eprintln!("{}", "No timer is running.".red());
+            eprintln!("Use `jrnl start \"[tag] what you are doing\"` to start one.");
+            process::exit(1);
+        }
+    };
+    let now = journal_now().max(started);
+
+    for (date, start, end) in split_by_day(started, now) {
+        let time = if end - start >= Duration::days(1) {
+            String::from("24h")
+        } else {
+            format!("{}-{}", start.format("%H:%M"), end.format("%H:%M"))
+        };
+        let line = format!("- {} {}", time, record);
+        add_record_to_entry(date, &line);
+        println!(
+            "{} [{}] {} ({})",
+            "Added:".green().bold(),
+            date.format("%Y-%m-%d").to_string().cyan(),
+            line,
+            format_minutes((end - start).num_minutes())
+        );
+    }
+    match fs::remove_file(timer_file()) {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
+    }
+}
+
+/// Prints the running timer, and for how long it has been running.
+pub fn print_timer_status() {
+    match read_timer() {
+        Some((started, record)) => println!(
+            "{} {} since {} ({})",
+            "Running:".green().bold(),
+            record,
+            started.format("%H:%M").to_string().cyan(),
+            format_minutes((journal_now() - started).num_minutes().max(0))
+        ),
+        None => println!("No timer is running."),
+    }
+}
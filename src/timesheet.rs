@@ -0,0 +1,342 @@
+//! Contains the functions for `jrnl timesheet`, which adds up the time spent, from records
+//! starting with a time range or a duration:
+//! ```text
+//! - 09:00-11:30 [work/projx] migration
+//! - 45m [work] standup
+//! - 1h30 [reading] Dune
+//! ```
+use crate::dates::DateSpan;
+use crate::funcs::*;
+use crate::layout::get_journal_files;
+use crate::utils::*;
+use chrono::NaiveTime;
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, process,
+};
+
+/// A record starting with a time range(`09:00-11:30`) or a duration(`45m`, `1h30`).
+#[derive(Debug, Clone)]
+pub struct TimeRecord {
+    /// Date(YYYY-MM-DD) of the entry containing the record
+    pub date: String,
+
+    /// Start of the time range, if it is a range
+    pub start: Option<NaiveTime>,
+
+    /// End of the time range, if it is a range
+    pub end: Option<NaiveTime>,
+
+    /// Time spent, in minutes
+    pub minutes: i64,
+
+    /// Tags of the record
+    pub tags: Vec<String>,
+
+    /// The record, without the time range or duration
+    pub text: String,
+}
+
+/// Returns the minutes in a duration like `45m`, `2h`, `1h30` or `1h30m`.
+///
+/// ## Example:
+/// ```
+/// assert_eq!(parse_duration("1h30"), Some(90));
+/// assert_eq!(parse_duration("45m"), Some(45));
+/// assert_eq!(parse_duration("hello"), None);
+/// assert_eq!(parse_duration("-30m"), None);
+/// ```
+pub fn parse_duration(word: &str) -> Option<i64> {
+    // A duration can't be negative(`-30m`) or signed(`+30m`)
+    if !word.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    if let Some(minutes) = word.strip_suffix('m')
+        && !minutes.contains('h')
+    {
+        return minutes.parse().ok();
+    }
+    let (hours, minutes) = word.split_once('h')?;
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = match minutes.trim_end_matches('m') {
+        "" => 0,
+        m => m.parse().ok().filter(|m| (0..60).contains(m))?,
+    };
+    Some(hours * 60 + minutes)
+}
+
+/// Returns the time range or duration at the start of a record, along with the rest of it,
+/// as (start, end, minutes, rest). A range ending before it starts goes past midnight.
+///
+/// ## Example:
+/// ```
+/// let (start, end, minutes, rest) = parse_time_record("- 22:30-01:00 [work] deploy").unwrap();
+/// assert_eq!(minutes, 150);
+/// assert_eq!(rest, "[work] deploy");
+/// ```
+pub fn parse_time_record(
+    line: &str,
+) -> Option<(Option<NaiveTime>, Option<NaiveTime>, i64, String)> {
+    // Tasks(`- [x] 09:00-10:00 ...`) can have a time too
+    let record = match parse_task(line) {
+        Some((_, text)) => text,
+        None => line.trim().strip_prefix("- ")?.trim_start().to_string(),
+    };
+    let (first, rest) = record.split_once(' ').unwrap_or((&record, ""));
+    let rest = rest.trim().to_string();
+
+    if let Some((start, end)) = first.split_once('-')
+        && let Ok(start) = NaiveTime::parse_from_str(start, "%H:%M")
+        && let Ok(end) = NaiveTime::parse_from_str(end, "%H:%M")
+    {
+        let mut minutes = (end - start).num_minutes();
+        if minutes < 0 {
+            minutes += 24 * 60;
+        }
+        return Some((Some(start), Some(end), minutes, rest));
+    }
+    let minutes = parse_duration(first)?;
+    Some((None, None, minutes, rest))
+}
+
+/// Returns all the time records in the span, sorted by date.
+///
+/// If `tag` is given, only the records with that tag(or one of its children) are returned.
+pub fn get_time_records(span: &DateSpan, tag: Option<&str>) -> Vec<TimeRecord> {
+    let start = span.start.format("%Y-%m-%d").to_string();
+    let end = span.end.format("%Y-%m-%d").to_string();
+    let mut records: Vec<TimeRecord> = Vec::new();
+    for (_, _, filename) in get_journal_files() {
+        let contents = fs::read_to_string(&filename).unwrap_or_default();
+        let mut entry_date = String::new();
+        for line in contents.lines() {
+            if line.starts_with("### ") {
+                continue;
+            }
+            if line.starts_with("# ") {
+                entry_date = line[1..].trim().to_string();
+                continue;
+            }
+            if entry_date < start || entry_date > end {
+                continue;
+            }
+            let Some((start_time, end_time, minutes, text)) = parse_time_record(line) else {
+                continue;
+            };
+            let tags = get_tags_from_line(line);
+            if let Some(tag) = tag
+                && !tags.iter().any(|t| tag_matches(t, tag))
+            {
+                continue;
+            }
+            records.push(TimeRecord {
+                date: entry_date.clone(),
+                start: start_time,
+                end: end_time,
+                minutes,
+                tags,
+                text,
+            });
+        }
+    }
+    records.sort_by(|a, b| a.date.cmp(&b.date));
+    records
+}
+
+/// Formats minutes as hours and minutes, like "2h 30m".
+pub fn format_minutes(minutes: i64) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Prints the time spent in the span, per day and per tag.
+///
+/// # Sample output:
+/// Note that the headings are colored in green.
+///
+/// ```text
+/// Timesheet for 2026-10-01 to 2026-10-18
+///
+/// ╭────────────┬─────────┬─────────╮
+/// │ Date       ┆ Records ┆ Time    │
+/// ╞════════════╪═════════╪═════════╡
+/// │ 2026-10-12 ┆ 3       ┆ 6h 15m  │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┤
+/// │ 2026-10-13 ┆ 2       ┆ 4h 00m  │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┤
+/// │ Total      ┆ 5       ┆ 10h 15m │
+/// ╰────────────┴─────────┴─────────╯
+///
+/// ╭────────────┬─────────╮
+/// │ Tag        ┆ Time    │
+/// ╞════════════╪═════════╡
+/// │ work       ┆ 10h 15m │
+/// ├╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌┤
+/// │  └ projx   ┆ 7h 30m  │
+/// ╰────────────┴─────────╯
+/// ```
+///
+/// A record with several tags counts for each of them, and the time of hierarchical
+/// tags(`[work/projx]`) is rolled up under their parent, like in the report.
+pub fn print_timesheet(span: &DateSpan, tag: Option<&str>) {
+    let records = get_time_records(span, tag);
+    if records.is_empty() {
+        eprintln!(
+            "{}",
+            "No records with a time range(09:00-11:30) or a duration(45m, 1h30) found.".red()
+        );
+        process::exit(1);
+    }
+
+    // Sorted by date
+    let mut per_day: BTreeMap<&str, (usize, i64)> = BTreeMap::new();
+    let mut per_tag: BTreeMap<String, i64> = BTreeMap::new();
+    for record in records.iter() {
+        let day = per_day.entry(&record.date).or_insert((0, 0));
+        day.0 += 1;
+        day.1 += record.minutes;
+        if record.tags.is_empty() {
+            *per_tag.entry("(untagged)".to_string()).or_insert(0) += record.minutes;
+        }
+        // `[work/a] [work/b]` counts only once for `work`
+        let mut tags: BTreeSet<String> = BTreeSet::new();
+        for t in record.tags.iter() {
+            let parts: Vec<&str> = t.split('/').collect();
+            for i in 1..=parts.len() {
+                tags.insert(parts[..i].join("/"));
+            }
+        }
+        for t in tags {
+            *per_tag.entry(t).or_insert(0) += record.minutes;
+        }
+    }
+
+    let mut day_table = Table::new();
+    day_table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Date".green(), "Records".green(), "Time".green()]);
+    for (date, (count, minutes)) in per_day.iter() {
+        day_table.add_row(vec![
+            date.to_string(),
+            count.to_string(),
+            format_minutes(*minutes),
+        ]);
+    }
+    let total: i64 = records.iter().map(|record| record.minutes).sum();
+    day_table.add_row(vec![
+        "Total".bold().to_string(),
+        records.len().to_string().bold().to_string(),
+        format_minutes(total).bold().to_string(),
+    ]);
+
+    let mut tag_table = Table::new();
+    tag_table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Tag".green(), "Time".green()]);
+    for root in tag_children(&per_tag, None) {
+        tag_table.add_row(vec![root.cyan().to_string(), format_minutes(per_tag[root])]);
+        add_tag_rows(&mut tag_table, &per_tag, root, " ");
+    }
+
+    let output = format!(
+        "{}\n\n{}\n\n{}\n",
+        format!(
+            "Timesheet for {} to {}",
+            span.start.format("%Y-%m-%d"),
+            span.end.format("%Y-%m-%d")
+        )
+        .bold()
+        .cyan()
+        .underline(),
+        day_table,
+        tag_table
+    );
+    print_with_pager(&output, per_day.len() + per_tag.len());
+}
+
+/// Returns the direct children of `parent`(or the top-level tags if `None`),
+/// sorted by the time spent.
+fn tag_children<'a>(per_tag: &'a BTreeMap<String, i64>, parent: Option<&str>) -> Vec<&'a String> {
+    let mut children: Vec<&String> = per_tag
+        .keys()
+        .filter(|t| match parent {
+            None => !t.contains('/'),
+            Some(p) => t
+                .strip_prefix(&format!("{}/", p))
+                .is_some_and(|rest| !rest.contains('/')),
+        })
+        .collect();
+    children.sort_by_key(|t| std::cmp::Reverse(per_tag[*t]));
+    children
+}
+
+/// Adds the rows for all descendants of `parent` to the table, indented as a tree.
+fn add_tag_rows(table: &mut Table, per_tag: &BTreeMap<String, i64>, parent: &str, indent: &str) {
+    let children = tag_children(per_tag, Some(parent));
+    for (i, child) in children.iter().enumerate() {
+        let last = i == children.len() - 1;
+        let name = child.rsplit('/').next().unwrap_or(child);
+        table.add_row(vec![
+            format!("{}{} {}", indent, if last { "└" } else { "├" }, name.cyan()),
+            format_minutes(per_tag[*child]),
+        ]);
+        let next_indent = format!("{}{}  ", indent, if last { " " } else { "│" });
+        add_tag_rows(table, per_tag, child, &next_indent);
+    }
+}
+
+/// Quotes a CSV field, if needed.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Returns the time records in the span as CSV, one record per row:
+/// ```text
+/// date,start,end,minutes,hours,tags,record
+/// 2026-10-12,09:00,11:30,150,2.50,work/projx,migration
+/// 2026-10-12,,,45,0.75,work,standup
+/// ```
+pub fn timesheet_csv(span: &DateSpan, tag: Option<&str>) -> String {
+    let mut output = String::from("date,start,end,minutes,hours,tags,record\n");
+    for record in get_time_records(span, tag) {
+        let time = |t: Option<NaiveTime>| t.map(|t| t.format("%H:%M").to_string());
+        // The tags are in their own column
+        let mut text = record.text.clone();
+        for t in record.tags.iter() {
+            text = text.replace(&format!("[{}]", t), "");
+        }
+        output.push_str(&format!(
+            "{},{},{},{},{:.2},{},{}\n",
+            record.date,
+            time(record.start).unwrap_or_default(),
+            time(record.end).unwrap_or_default(),
+            record.minutes,
+            record.minutes as f64 / 60.0,
+            csv_field(&record.tags.join(" ")),
+            csv_field(&text.split_whitespace().collect::<Vec<&str>>().join(" "))
+        ));
+    }
+    output
+}
+
+/// Prints the CSV of the time records, or writes it to `filename` if it is not empty.
+pub fn export_timesheet_csv(span: &DateSpan, tag: Option<&str>, filename: &str) {
+    let csv = timesheet_csv(span, tag);
+    if filename.is_empty() {
+        print!("{}", csv);
+        return;
+    }
+    match fs::write(shellexpand::tilde(filename).into_owned(), csv) {
+        Ok(_) => println!("{} {}", "Wrote the timesheet to".green(), filename.bold()),
+        Err(e) => panic!("An error occured: {}", e),
+    }
+}
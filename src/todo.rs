@@ -0,0 +1,214 @@
+//! Contains the functions for `jrnl todo`, to keep track of tasks written in the entries.
+//!
+//! A task is any record with a Markdown checkbox:
+//! ```text
+//! - [ ] call the bank
+//! - [x] pay the rent
+//! ```
+use crate::funcs::*;
+use crate::layout::get_journal_files;
+use crate::lock::*;
+use crate::utils::*;
+use chrono::NaiveDate;
+use colored::Colorize;
+use comfy_table::{ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL};
+use std::{fs, process};
+
+/// A task found in one of the journal files.
+#[derive(Debug, Clone)]
+pub struct Task {
+    /// Date(YYYY-MM-DD) of the entry containing the task
+    pub date: String,
+
+    /// The journal file containing the task
+    pub filename: String,
+
+    /// Line number(0-indexed) of the task in the file
+    pub line_no: usize,
+
+    /// State of the checkbox: ' '(open), 'x'(done), '>'(carried over)
+    pub state: char,
+
+    /// The task itself, without the checkbox
+    pub text: String,
+}
+
+/// Returns all tasks in the journal, sorted by date.
+pub fn get_all_tasks() -> Vec<Task> {
+    let mut tasks: Vec<Task> = Vec::new();
+    for (_, _, filename) in get_journal_files() {
+        let contents = fs::read_to_string(&filename).unwrap_or_default();
+        let mut entry_date = String::new();
+        for (i, line) in contents.split('\n').enumerate() {
+            if line.starts_with("# ") {
+                entry_date = line[1..].trim().to_string();
+                continue;
+            }
+            if let Some((state, text)) = parse_task(line) {
+                tasks.push(Task {
+                    date: entry_date.clone(),
+                    filename: filename.clone(),
+                    line_no: i,
+                    state,
+                    text,
+                });
+            }
+        }
+    }
+    // Stable sort, so tasks of the same day stay in order
+    tasks.sort_by(|a, b| a.date.cmp(&b.date));
+    tasks
+}
+
+/// Returns all open tasks, sorted by date. The ID of a task is its position(starting at 1)
+/// in this list.
+pub fn get_open_tasks() -> Vec<Task> {
+    get_all_tasks()
+        .into_iter()
+        .filter(|task| task.state == ' ')
+        .collect()
+}
+
+/// Prints all the open tasks in the journal, along with their ID and date.
+///
+/// # Sample output:
+/// Note that the headings are colored in green.
+///
+/// ```text
+/// ╭────┬───────────────┬─────────────────╮
+/// │ ID ┆ Date of Entry ┆ Task            │
+/// ╞════╪═══════════════╪═════════════════╡
+/// │ 1  ┆ 2025-03-27    ┆ call the bank   │
+/// ├╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌┤
+/// │ 2  ┆ 2025-03-29    ┆ [work] review   │
+/// ╰────┴───────────────┴─────────────────╯
+/// ```
+pub fn print_open_tasks() {
+    let tasks = get_open_tasks();
+    if tasks.is_empty() {
+        println!("{}", "No open tasks. Well done!".green());
+        return;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["ID".green(), "Date of Entry".green(), "Task".green()]);
+    for (i, task) in tasks.iter().enumerate() {
+        table.add_row(vec![
+            (i + 1).to_string(),
+            task.date.clone(),
+            task.text.clone(),
+        ]);
+    }
+    print_with_pager(&format!("{}", table), tasks.len());
+}
+
+/// Changes the checkbox of a task to `state`, in its journal file. Returns `false` if the task
+/// is no longer in the entry, or already has that state.
+///
+/// The journal may have changed since the task was found, so it is looked for again if it
+/// is not at `task.line_no` anymore.
+pub fn set_task_state(task: &Task, state: char) -> bool {
+    let _lock = lock_journal();
+    let contents = match fs::read_to_string(&task.filename) {
+        Ok(data) => data,
+        Err(e) => panic!("Error reading file: {}", e),
+    };
+    let mut lines: Vec<String> = contents.split('\n').map(|s| s.to_string()).collect();
+    let line_no = match find_task_line(&lines, task) {
+        Some(line_no) => line_no,
+        None => return false,
+    };
+    let line = &lines[line_no];
+    // Right after the `- [` found by `parse_task`
+    let checkbox = line.find("- [").unwrap_or_default() + 3;
+    let changed = format!("{}{}{}", &line[..checkbox], state, &line[checkbox + 1..]);
+    if changed == *line {
+        return false;
+    }
+    lines[line_no] = changed;
+    match write_journal(&task.filename, &lines.join("\n")) {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
+    }
+    true
+}
+
+/// Returns the line number of the task in `lines`, which is `task.line_no` unless the
+/// journal changed. Otherwise, the first task with the same state and text in its entry.
+fn find_task_line(lines: &[String], task: &Task) -> Option<usize> {
+    let is_task = |line: &String| parse_task(line) == Some((task.state, task.text.clone()));
+    if lines.get(task.line_no).is_some_and(is_task) {
+        return Some(task.line_no);
+    }
+    let mut entry_date = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("# ") {
+            entry_date = line[1..].trim().to_string();
+        } else if entry_date == task.date && is_task(line) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Marks the open task with the given ID(as shown by `jrnl todo`) as done.
+pub fn mark_task_done(id: usize) {
+    let tasks = get_open_tasks();
+    let task = match id.checked_sub(1).and_then(|i| tasks.get(i)) {
+        Some(task) => task,
+        None => {
+            eprintln!(
+                "{}",
+                format!("There is no open task with the ID {}", id).red()
+            );
+            eprintln!("Use `jrnl todo` to see all open tasks.");
+            process::exit(1);
+        }
+    };
+    if !set_task_state(task, 'x') {
+        eprintln!("{}", "The journal changed; run `jrnl todo` again.".red());
+        process::exit(1);
+    }
+    println!(
+        "{} [{}] {}",
+        "Done:".green().bold(),
+        task.date.cyan(),
+        task.text
+    );
+}
+
+/// Returns the open tasks of the latest entry before `date`, and marks them as
+/// carried over(`- [>]`) in that entry.
+///
+/// Used by [`add_info_to_file`] for today's entry, when `carry_over_tasks` is set in the config.
+pub fn carry_over_tasks(date: NaiveDate) -> Vec<String> {
+    let date = date.format("%Y-%m-%d").to_string();
+    let tasks = get_all_tasks();
+
+    // The latest entry before `date`, even if it has no tasks
+    let mut previous_entry = String::new();
+    for (_, _, filename) in get_journal_files() {
+        let (headings, _) = get_headings(&filename);
+        for head in headings {
+            let head = head[1..].trim().to_string();
+            if head < date && head > previous_entry {
+                previous_entry = head;
+            }
+        }
+    }
+
+    let mut output: Vec<String> = Vec::new();
+    for task in tasks
+        .iter()
+        .filter(|task| task.date == previous_entry && task.state == ' ')
+    {
+        if set_task_state(task, '>') {
+            output.push(task.text.clone());
+        }
+    }
+    output
+}
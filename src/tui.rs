@@ -0,0 +1,303 @@
+//! Contains the full-screen interactive browser, opened with `jrnl tui`.
+//!
+//! It uses the same functions as the CLI([`get_entry`], [`try_search_in_span`], etc.)
+//! to read the journal, so the results are the same as the ones printed by the CLI.
+//!
+//! Keys:
+//! - `←↓↑→`/`hjkl`: move between days
+//! - `n`/`p`: next/previous month
+//! - `t`: jump to today
+//! - `/`: filter by a tag or a word(tags are preferred, as in `-t`)
+//! - `Esc`: clear the filter
+//! - `Enter`/`e`: open the selected day in the editor
+//! - `q`: quit
+use crate::dates::DateSpan;
+use crate::funcs::*;
+use crate::layout::{config_layout, get_journal_files_between};
+use crate::utils::*;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, Paragraph, Wrap},
+};
+use std::{fs, io};
+
+/// State of the interactive browser
+struct App {
+    /// The day currently selected in the calendar
+    selected: NaiveDate,
+
+    /// The tag or word being filtered for
+    filter: String,
+
+    /// Whether the filter is being typed
+    editing_filter: bool,
+
+    /// Whether the filter matched a tag(otherwise it is a word search)
+    tag_mode: bool,
+
+    /// Days of the selected month which have an entry
+    entry_days: Vec<u32>,
+
+    /// Records matching the filter in the selected month, as (date, record)
+    matches: Vec<(String, String)>,
+
+    /// The entry of the selected day
+    preview: String,
+}
+
+impl App {
+    fn new() -> App {
+        let mut app = App {
+            selected: crate::dates::journal_today(),
+            filter: String::new(),
+            editing_filter: false,
+            tag_mode: true,
+            entry_days: Vec::new(),
+            matches: Vec::new(),
+            preview: String::new(),
+        };
+        app.refresh();
+        app
+    }
+
+    /// Re-reads the selected month's journal files, and updates everything shown.
+    fn refresh(&mut self) {
+        let span = DateSpan::month(self.selected.year(), self.selected.month()).unwrap();
+        let files = get_journal_files_between(span.start, span.end);
+        self.entry_days.clear();
+        self.matches.clear();
+        self.preview = get_entry(self.selected);
+
+        // Nothing here may exit, as the terminal would be left in raw mode; the files are
+        // read directly, instead of with the CLI functions which exit when one is missing.
+        let contents: Vec<String> = files
+            .iter()
+            .map(|filename| fs::read_to_string(filename).unwrap_or_default())
+            .collect();
+        for line in contents.iter().flat_map(|contents| contents.lines()) {
+            // A file can have entries of other months, like a yearly file
+            if let Some(heading) = line.strip_prefix("# ")
+                && let Ok(date) = NaiveDate::parse_from_str(heading.trim(), "%Y-%m-%d")
+                && date >= span.start
+                && date <= span.end
+            {
+                self.entry_days.push(date.day());
+            }
+        }
+
+        if !self.filter.is_empty() {
+            // Prefer tags, like the hint given by `-s` when a tag with the same name exists
+            self.tag_mode = contents
+                .iter()
+                .flat_map(|contents| contents.lines())
+                .flat_map(get_tags_from_line)
+                .any(|tag| tag_matches(&tag, &self.filter));
+            if let Some((dates, records)) =
+                try_search_in_span(&self.filter, &span, !self.tag_mode, 0)
+            {
+                self.matches = dates.into_iter().zip(records).collect();
+            }
+        }
+    }
+
+    /// Moves the selection by some days.
+    fn move_days(&mut self, days: i64) {
+        self.selected += Duration::days(days);
+        self.refresh();
+    }
+
+    /// Moves the selection by some months.
+    fn move_months(&mut self, months: i32) {
+        self.selected = if months >= 0 {
+            self.selected + Months::new(months as u32)
+        } else {
+            self.selected - Months::new(-months as u32)
+        };
+        self.refresh();
+    }
+
+    /// Days of the selected month which have a record matching the filter
+    fn match_days(&self) -> Vec<u32> {
+        self.matches
+            .iter()
+            .filter_map(|(date, _)| date.split('-').nth(2)?.trim().parse().ok())
+            .collect()
+    }
+}
+
+/// Runs the interactive browser, until the user quits.
+pub fn run_tui() -> io::Result<()> {
+    // All output is rendered by ratatui, so the ANSI colors from the CLI functions must go.
+    colored::control::set_override(false);
+    // Checked before the terminal is taken over, since an invalid `layout` exits
+    config_layout();
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut terminal);
+    ratatui::restore();
+    colored::control::unset_override();
+    result
+}
+
+fn run_app(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    let mut app = App::new();
+    loop {
+        terminal.draw(|frame| draw(frame, &app))?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+
+        if app.editing_filter {
+            match key.code {
+                KeyCode::Enter => app.editing_filter = false,
+                KeyCode::Esc => {
+                    app.editing_filter = false;
+                    app.filter.clear();
+                }
+                KeyCode::Backspace => {
+                    app.filter.pop();
+                }
+                KeyCode::Char(c) => app.filter.push(c),
+                _ => continue,
+            }
+            app.refresh();
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Left | KeyCode::Char('h') => app.move_days(-1),
+            KeyCode::Right | KeyCode::Char('l') => app.move_days(1),
+            KeyCode::Up | KeyCode::Char('k') => app.move_days(-7),
+            KeyCode::Down | KeyCode::Char('j') => app.move_days(7),
+            KeyCode::Char('n') => app.move_months(1),
+            KeyCode::Char('p') => app.move_months(-1),
+            KeyCode::Char('t') => {
+                app.selected = crate::dates::journal_today();
+                app.refresh();
+            }
+            KeyCode::Char('/') => app.editing_filter = true,
+            KeyCode::Esc => {
+                app.filter.clear();
+                app.refresh();
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                ratatui::restore();
+                colored::control::unset_override();
+                open_editor(app.selected.format("%Y-%m-%d").to_string());
+                colored::control::set_override(false);
+                *terminal = ratatui::init();
+                app.refresh();
+            }
+            _ => (),
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [main_area, status_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [left_area, preview_area] =
+        Layout::horizontal([Constraint::Length(24), Constraint::Min(0)]).areas(main_area);
+    let [calendar_area, matches_area] =
+        Layout::vertical([Constraint::Length(10), Constraint::Min(0)]).areas(left_area);
+
+    frame.render_widget(
+        Paragraph::new(calendar_lines(app)).block(Block::bordered().title(" Calendar ")),
+        calendar_area,
+    );
+
+    let matches_title = if app.filter.is_empty() {
+        " Matches ".to_string()
+    } else if app.tag_mode {
+        format!(" Tag: {} ", app.filter)
+    } else {
+        format!(" Search: {} ", app.filter)
+    };
+    let items: Vec<ListItem> = app
+        .matches
+        .iter()
+        .map(|(date, record)| {
+            ListItem::new(vec![
+                Line::from(date.clone().cyan()),
+                Line::from(format!("  {}", record)),
+            ])
+        })
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title(matches_title)),
+        matches_area,
+    );
+
+    frame.render_widget(
+        Paragraph::new(app.preview.clone())
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(format!(" {} ", app.selected.format("%Y-%m-%d %A")))),
+        preview_area,
+    );
+
+    let status = if app.editing_filter {
+        Line::from(vec![
+            Span::styled("Filter: ", Style::default().fg(Color::Yellow)),
+            Span::raw(app.filter.clone()),
+            Span::styled("█", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+        ])
+    } else {
+        Line::from(
+            "hjkl/arrows: move  n/p: month  t: today  /: filter  Esc: clear  e/Enter: edit  q: quit"
+                .dark_gray(),
+        )
+    };
+    frame.render_widget(Paragraph::new(status), status_area);
+}
+
+/// Builds the calendar of the selected month, in the same layout as [`print_calendar`].
+///
+/// Days with an entry are green and bold, days matching the filter are cyan,
+/// and the selected day is reversed.
+fn calendar_lines(app: &App) -> Vec<Line<'static>> {
+    let year = app.selected.year();
+    let month = app.selected.month();
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let last_day = (first_day + Months::new(1)).pred_opt().unwrap();
+    let match_days = app.match_days();
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(
+            format!("{} {}", month_no_to_name(month), year)
+                .cyan()
+                .bold(),
+        ),
+        Line::from(crate::dates::weekdays_header().yellow()),
+    ];
+
+    let first_weekday = crate::dates::days_from_week_start(first_day.weekday());
+    let mut spans: Vec<Span> = vec![Span::raw("   ".repeat(first_weekday as usize))];
+    for day in 1..=last_day.day() {
+        let mut style = Style::default();
+        if app.entry_days.contains(&day) {
+            style = style.fg(Color::Green).add_modifier(Modifier::BOLD);
+        }
+        if match_days.contains(&day) {
+            style = style.fg(Color::Cyan).add_modifier(Modifier::UNDERLINED);
+        }
+        if day == app.selected.day() {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        spans.push(Span::styled(format!("{:>2}", day), style));
+        spans.push(Span::raw(" "));
+        if (first_weekday + day).is_multiple_of(7) {
+            lines.push(Line::from(std::mem::take(&mut spans)));
+        }
+    }
+    if !spans.is_empty() {
+        lines.push(Line::from(spans));
+    }
+    lines
+}
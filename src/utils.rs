@@ -3,9 +3,13 @@
 //! This is majorly just a bunch of functions thrown together that works.
 //!
 //! It is not very efficient, nor idiomatic, but it works(and is not noticeably slow).
+use crate::dates::DateSpan;
 use crate::funcs::*;
 use crate::get_default_path;
-use chrono::{DateTime, Datelike, Local, NaiveDate, format::ParseErrorKind};
+use crate::layout::*;
+use crate::lock::*;
+use crate::todo::carry_over_tasks;
+use chrono::{Datelike, NaiveDate, NaiveTime};
 use colored::Colorize;
 use comfy_table::{
     ContentArrangement, Table,
@@ -15,8 +19,9 @@ use comfy_table::{
 use serde::Deserialize;
 use std::{
     collections::HashMap,
-    fs::{self, File, OpenOptions},
+    fs::{self, File},
     io::{BufRead, BufReader, ErrorKind, Write},
+    path::Path,
     process,
 };
 use stringmetrics::levenshtein;
@@ -38,8 +43,8 @@ pub struct Config {
     /// The default pager to be chosen
     pub pager: String,
 
-    /// Maximum number of rows that is supposed to be shown when showing the tags when using [`gen_report`]
-    /// or [`gen_report_year`]
+    /// Maximum number of rows that is supposed to be shown when showing the tags when using
+    /// [`gen_report`][crate::report::gen_report]
     pub max_rows: u32,
 
     /// Whether the timestamp must be added(next to the weekday, if present)
@@ -53,6 +58,68 @@ pub struct Config {
 
     /// The default approximation to be used when no number is passed to the `--approx` flag.
     pub approx_variation: u32,
+
+    /// Whether the unfinished tasks(`- [ ] task`) of the previous entry should be copied
+    /// into today's entry, when it is made
+    #[serde(default)]
+    pub carry_over_tasks: bool,
+
+    /// The timezone(like "Asia/Kolkata") used to figure out today's date and the timestamps.
+    /// The local timezone is used if empty
+    #[serde(default)]
+    pub timezone: String,
+
+    /// The time(HH:MM) at which a new day starts. Before it, "today" is still the previous date.
+    /// Midnight is used if empty
+    #[serde(default)]
+    pub day_starts_at: String,
+
+    /// The day a week starts on, "monday" or "sunday", for the calendars and weeks.
+    /// Monday is used if empty
+    #[serde(default)]
+    pub week_start: String,
+
+    /// Whether `--gen-report` should start with the entries written on this day in previous years
+    #[serde(default)]
+    pub on_this_day_in_report: bool,
+
+    /// How the entries are stored in files: "monthly", "daily", "yearly", or a pattern like
+    /// "entries/{YYYY}-{MM}.md". See [`crate::layout`]. Monthly is used if empty
+    #[serde(default)]
+    pub layout: String,
+
+    /// The folder for `jrnl backup`. `jrnl_backups`, next to `jrnl_folder`, is used if empty
+    #[serde(default)]
+    pub backup_folder: String,
+
+    /// How many days, weeks and months to keep the latest backup of. Older backups are removed
+    /// after each backup. If all are 0, every backup is kept
+    #[serde(default = "default_backup_keep_daily")]
+    pub backup_keep_daily: u32,
+    #[serde(default = "default_backup_keep_weekly")]
+    pub backup_keep_weekly: u32,
+    #[serde(default = "default_backup_keep_monthly")]
+    pub backup_keep_monthly: u32,
+
+    /// Whether to back up the journal before rewriting many files(`jrnl tags rename`,
+    /// `jrnl import`, `jrnl migrate`, `jrnl restore`)
+    #[serde(default = "default_backup_before_rewrite")]
+    pub backup_before_rewrite: bool,
+}
+
+// The defaults of the backup settings, for config files made before they were added.
+// They are the same as in the default config file.
+fn default_backup_keep_daily() -> u32 {
+    7
+}
+fn default_backup_keep_weekly() -> u32 {
+    4
+}
+fn default_backup_keep_monthly() -> u32 {
+    12
+}
+fn default_backup_before_rewrite() -> bool {
+    true
 }
 
 impl std::fmt::Display for Config {
@@ -78,6 +145,65 @@ impl std::fmt::Display for Config {
             "Approximation sensitivity ",
             &self.approx_variation.to_string(),
         ]);
+        table.add_row(vec![
+            "Carry over unfinished tasks",
+            &self.carry_over_tasks.to_string(),
+        ]);
+        table.add_row(vec![
+            "Timezone",
+            if self.timezone.is_empty() {
+                "local"
+            } else {
+                &self.timezone
+            },
+        ]);
+        table.add_row(vec![
+            "Day starts at",
+            if self.day_starts_at.is_empty() {
+                "00:00"
+            } else {
+                &self.day_starts_at
+            },
+        ]);
+        table.add_row(vec![
+            "Week starts on",
+            if self.week_start.is_empty() {
+                "monday"
+            } else {
+                &self.week_start
+            },
+        ]);
+        table.add_row(vec![
+            "On this day in reports",
+            &self.on_this_day_in_report.to_string(),
+        ]);
+        table.add_row(vec![
+            "Layout",
+            if self.layout.is_empty() {
+                "monthly"
+            } else {
+                &self.layout
+            },
+        ]);
+        table.add_row(vec![
+            "Backup folder",
+            if self.backup_folder.is_empty() {
+                "jrnl_backups"
+            } else {
+                &self.backup_folder
+            },
+        ]);
+        table.add_row(vec![
+            "Backups kept(daily, weekly, monthly)",
+            &format!(
+                "{}, {}, {}",
+                self.backup_keep_daily, self.backup_keep_weekly, self.backup_keep_monthly
+            ),
+        ]);
+        table.add_row(vec![
+            "Back up before rewriting",
+            &self.backup_before_rewrite.to_string(),
+        ]);
         write!(
             f,
             "{}\n{}",
@@ -135,53 +261,60 @@ pub fn get_headings(filename: &str) -> (Vec<String>, Vec<u32>) {
 pub fn add_info_to_file(filename: &str, date: String) -> std::io::Result<()> {
     // Convert string date to NaiveDate to get the weekday
     let date_naive = NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap();
-    let weekday = date_naive.weekday().to_string().to_uppercase();
-    let timestamp = Local::now().format("%H:%M:%S").to_string();
-
-    let mut file_data: File = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .open(filename)
-        .unwrap();
+    let timestamp = crate::dates::journal_now().time();
+
+    // Checking for the heading and adding it must not be split by another `jrnl`
+    let _lock = lock_journal();
     // We don't need the line numbers
     let (headings, _) = get_headings(filename);
 
     let config = read_config().0;
-    let mut input_str = String::new();
-    if config.add_weekday {
-        input_str.push_str(&format!("\n### {}", &weekday));
-    }
-    if config.add_timestamp && config.add_weekday {
-        input_str.push_str(&format!(" ({})", &timestamp));
-    } else if config.add_timestamp && !config.add_weekday {
-        input_str.push_str(&format!("\n### ({})", &timestamp));
-    }
-    input_str.push_str(&format!("\n# {}", &date));
+    let mut input_str = format!("\n{}", entry_heading(date_naive, Some(timestamp)));
     if config.add_food_column {
         input_str.push_str("\n- [food] | | | ");
     }
 
     // If `headings` doesn't contain today's date, then append it to the file.
     if !headings.contains(&format!("# {}", &date)) {
-        let write_result = file_data.write(input_str.as_bytes());
-        match write_result {
-            Ok(_) => (),
-            Err(e) => panic!("An error: {}", e),
+        // Only carried forward, into today's entry; not into an entry added for a past date
+        if config.carry_over_tasks && date_naive == crate::dates::journal_today() {
+            for task in carry_over_tasks(date_naive) {
+                input_str.push_str(&format!("\n- [ ] {}", task));
+            }
         }
+        // Read only now, since carrying over the tasks might have rewritten this file.
+        let contents = fs::read_to_string(filename)?;
+        write_journal(filename, &(contents + &input_str))?;
     }
     Ok(())
 }
 
+/// Returns the heading of an entry, as set in the config(`add_weekday`, `add_timestamp`):
+/// ```text
+/// ### SATURDAY (21:40:12)
+/// # 2026-10-17
+/// ```
+/// The time is left out if it is not known.
+pub fn entry_heading(date: NaiveDate, time: Option<NaiveTime>) -> String {
+    let config = read_config().0;
+    let weekday = date.weekday().to_string().to_uppercase();
+    let time = time
+        .filter(|_| config.add_timestamp)
+        .map(|time| format!("({})", time.format("%H:%M:%S")));
+    let mut heading = match (config.add_weekday, time) {
+        (true, Some(time)) => format!("### {} {}\n", weekday, time),
+        (true, None) => format!("### {}\n", weekday),
+        (false, Some(time)) => format!("### {}\n", time),
+        (false, None) => String::new(),
+    };
+    heading.push_str(&format!("# {}", date.format("%Y-%m-%d")));
+    heading
+}
+
 /// Get a given date's entry
 pub fn get_entry(date: NaiveDate) -> String {
     // Get the filename(pre-defined format) from the NaiveDate
-    let filename: String = format!(
-        "{}/jrnl_folder/{}/{}_{}.md",
-        get_default_path(),
-        date.year(),
-        date.year(),
-        correct_month_nums(date.month())
-    );
+    let filename: String = journal_file(date);
     let entry_date: String = date.format("%Y-%m-%d").to_string();
     let weekday = date.weekday().to_string();
     let mut entry: String = String::new();
@@ -207,6 +340,7 @@ pub fn get_entry(date: NaiveDate) -> String {
     // Couple variables to make sure we only get values within the entry
     let mut reached_date_yet: bool = false;
     let mut finished_entry: bool = false;
+    let mut attachments: Vec<String> = Vec::new();
 
     for line in reader.lines() {
         let cur_line: String = match line {
@@ -236,7 +370,8 @@ pub fn get_entry(date: NaiveDate) -> String {
                 ));
             }
         }
-        if reached_date_yet && !finished_entry && !(cur_line == "") {
+        if reached_date_yet && !finished_entry && !(cur_line.is_empty()) {
+            attachments.extend(crate::attachments::get_attachments_from_line(&cur_line));
             // Color the tags
             if cur_line.contains("[") {
                 // Split the current line into parts, one or more of which contain a tag
@@ -255,86 +390,290 @@ pub fn get_entry(date: NaiveDate) -> String {
         }
     }
 
-    if entry == "" {
+    if entry.is_empty() {
         entry.push_str(&format!(
             "{}",
             format!("Entry does not exist for {}", entry_date).red()
         ));
     }
+    if !attachments.is_empty() {
+        entry.push_str(&format!("{}\n", "Attachments:".yellow().bold()));
+        for attachment in attachments {
+            let path = crate::attachments::attachment_path(&attachment);
+            if Path::new(&path).exists() {
+                entry.push_str(&format!("  {}\n", path.underline()));
+            } else {
+                entry.push_str(&format!("  {} {}\n", path, "(missing)".red()));
+            }
+        }
+    }
     entry
 }
 
-/// Returns the tags found from a file(a month)
-/// Useful in --gen-report
-pub fn get_tags_from_file(filename: &str) -> Vec<String> {
-    let file_result: Result<File, std::io::Error> = File::open(filename);
-    let file: File = match file_result {
-        Ok(file) => file,
-        Err(e) => match e.kind() {
-            // If the file is not found, say that it doesn't exist, instead of panicking.
-            ErrorKind::NotFound => {
-                eprintln!("Entry does not exist for {}", filename);
-                return vec![];
-            }
-            other => panic!("Error opening file: {other}"),
-        },
-    };
+/// Returns the tags(`[tag]`) found in a single line.
+///
+/// The checkbox of a task(`- [ ] task`, `- [x] task`), the wiki links(`[[2026-10-01]]`)
+/// and the Markdown links(`[photo.jpg](../attachments/...)`) are not counted as tags.
+pub fn get_tags_from_line(line: &str) -> Vec<String> {
+    get_tag_spans(line)
+        .into_iter()
+        .map(|(start, end)| line[start..end].to_string())
+        .collect()
+}
 
-    let reader: BufReader<File> = BufReader::new(file);
-    let mut tags: Vec<String> = Vec::new();
+/// Returns where the tags are in a single line, as the byte ranges of their names(without
+/// the brackets), so that they can be changed in place. See [`get_tags_from_line`].
+///
+/// ## Example:
+/// ```
+/// let line = "- [x] [work] paid [receipt.pdf](../r.pdf) [[events]]";
+/// assert_eq!(get_tag_spans(line), vec![(7, 11)]);
+/// ```
+pub fn get_tag_spans(line: &str) -> Vec<(usize, usize)> {
+    let bytes = line.as_bytes();
+    // The parts which can not have tags
+    let mut skipped = vec![false; bytes.len()];
+    if parse_task(line).is_some() {
+        let start = line.len() - line.trim_start().len();
+        let end = start + line[start..].find(']').unwrap_or_default() + 1;
+        skipped[start..end].fill(true);
+    }
+    // Wiki links, as in `split_links`
+    let mut from = 0;
+    while let Some(start) = line[from..].find("[[").map(|i| from + i) {
+        let Some(end) = line[start + 2..].find("]]").map(|i| start + 2 + i + 2) else {
+            break;
+        };
+        skipped[start..end].fill(true);
+        from = end;
+    }
+    // Markdown links, as in `split_markdown_links`, ignoring the wiki links
+    let mut from = 0;
+    while let Some(middle) = line[from..].find("](").map(|i| from + i) {
+        let Some(end) = line[middle + 2..].find(')').map(|i| middle + 2 + i + 1) else {
+            break;
+        };
+        if skipped[middle] {
+            from = middle + 2;
+            continue;
+        }
+        match line[from..middle].rfind('[').map(|i| from + i) {
+            Some(start) => {
+                skipped[start..end].fill(true);
+                from = end;
+            }
+            None => from = middle + 2,
+        }
+    }
 
-    for line in reader.lines() {
-        // Making another variable to manipulate it.
-        let cur_line: String = match line {
-            Ok(line) => line.clone(),
-            Err(e) => {
-                eprintln!("{}", e.to_string().red());
-                "".to_string()
+    // A tag is the text before a `]`, since the last `[` or `]`
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    let mut tag_start: Option<usize> = Some(0);
+    for (i, byte) in bytes.iter().enumerate() {
+        if skipped[i] {
+            tag_start = None;
+        } else if *byte == b'[' {
+            tag_start = Some(i + 1);
+        } else if *byte == b']' {
+            if let Some(start) = tag_start
+                && i > start
+            {
+                spans.push((start, i));
             }
+            tag_start = Some(i + 1);
+        }
+    }
+    spans
+}
+
+/// Splits the wiki links(`[[2026-10-01]]`, `[[projects/alpha|the project]]`) out of a line,
+/// as (line without the links, targets of the links).
+///
+/// ## Example:
+/// ```
+/// let (rest, links) = split_links("- [work] see [[2026-10-01]] and [[events]]");
+/// assert_eq!(rest, "- [work] see  and ");
+/// assert_eq!(links, vec!["2026-10-01", "events"]);
+/// ```
+pub fn split_links(line: &str) -> (String, Vec<String>) {
+    let mut rest = String::new();
+    let mut links: Vec<String> = Vec::new();
+    let mut remaining = line;
+    while let Some(start) = remaining.find("[[") {
+        let Some(end) = remaining[start + 2..].find("]]") else {
+            break;
+        };
+        let link = &remaining[start + 2..start + 2 + end];
+        // `[[target|label]]` links to the target
+        let target = link.split('|').next().unwrap_or_default().trim();
+        if !target.is_empty() {
+            links.push(target.to_string());
+        }
+        rest.push_str(&remaining[..start]);
+        remaining = &remaining[start + 2 + end + 2..];
+    }
+    rest.push_str(remaining);
+    (rest, links)
+}
+
+/// Splits the Markdown links(`[label](url)`) and images(`![label](url)`) out of a line,
+/// as (line without the links, urls of the links).
+///
+/// ## Example:
+/// ```
+/// let (rest, urls) = split_markdown_links("- [home] ![cat.jpg](../attachments/2026/10/ab12.jpg)");
+/// assert_eq!(rest, "- [home] ");
+/// assert_eq!(urls, vec!["../attachments/2026/10/ab12.jpg"]);
+/// ```
+pub fn split_markdown_links(line: &str) -> (String, Vec<String>) {
+    let mut rest = String::new();
+    let mut urls: Vec<String> = Vec::new();
+    let mut remaining = line;
+    while let Some(middle) = remaining.find("](") {
+        let Some(end) = remaining[middle + 2..].find(')') else {
+            break;
         };
+        let Some(start) = remaining[..middle].rfind('[') else {
+            // Not a link, like `a](b)`
+            rest.push_str(&remaining[..middle + 2]);
+            remaining = &remaining[middle + 2..];
+            continue;
+        };
+        let start = if remaining[..start].ends_with('!') {
+            start - 1
+        } else {
+            start
+        };
+        urls.push(remaining[middle + 2..middle + 2 + end].trim().to_string());
+        rest.push_str(&remaining[..start]);
+        remaining = &remaining[middle + 2 + end + 1..];
+    }
+    rest.push_str(remaining);
+    (rest, urls)
+}
 
-        if cur_line.contains("[") {
-            // Similar setup as in `get_entry()`, to collect the tags
-            let parts: Vec<&str> = cur_line.split_inclusive(&['[', ']'][..]).collect();
-            for part in parts.clone() {
-                if part.contains("]") {
-                    tags.push((&part[..part.len() - 1]).to_string());
+/// Returns all entries in the journal(sorted by date), along with the first
+/// non-empty record of each, as (date, first_line).
+pub fn get_all_entries() -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for (_, _, filename) in get_journal_files() {
+        let contents = fs::read_to_string(&filename).unwrap_or_default();
+        let mut first_line_found = true;
+        for line in contents.lines() {
+            if line.starts_with("### ") {
+                continue;
+            }
+            if line.starts_with("# ") {
+                entries.push((line[1..].trim().to_string(), String::new()));
+                first_line_found = false;
+                continue;
+            }
+            let record = line.trim().trim_start_matches("- ").trim();
+            // Skip empty records, and the empty food column
+            if first_line_found || record.is_empty() || record.replace(['|', ' '], "") == "[food]" {
+                continue;
+            }
+            if let Some(entry) = entries.last_mut() {
+                entry.1 = record.to_string();
+            }
+            first_line_found = true;
+        }
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// Returns all the notes in `jrnl_folder`, that is, every file other than the
+/// journal files(as per the `layout`), the year folders and the attachments(in `attachments/`).
+/// The names are relative to `jrnl_folder`.
+pub fn get_note_files() -> Vec<String> {
+    let root = format!("{}/jrnl_folder", get_default_path());
+    let mut notes: Vec<String> = Vec::new();
+    let mut dirs: Vec<String> = vec![String::new()];
+    while let Some(dir) = dirs.pop() {
+        let paths = match fs::read_dir(format!("{}/{}", root, dir)) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+        for path in paths.flatten() {
+            let name = path.file_name().to_string_lossy().to_string();
+            let rel_name = if dir.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", dir, name)
+            };
+            if path.path().is_dir() {
+                // Year folders contain the month files
+                let is_year = dir.is_empty() && name.len() == 4 && name.parse::<i32>().is_ok();
+                let is_attachments = dir.is_empty() && name == "attachments";
+                if !is_year && !is_attachments && !name.starts_with('.') {
+                    dirs.push(rel_name);
                 }
+            } else if !name.starts_with('.') && !is_journal_file(&rel_name) {
+                notes.push(rel_name);
             }
         }
     }
-    tags
+    notes.sort();
+    notes
 }
 
-/// Returns all records with the tag in the given file.
+/// If the line is a task(a record with a Markdown checkbox), returns the state of the
+/// checkbox, and the rest of the record.
+///
+/// The state is one of ' '(open), 'x'(done), or '>'(carried over to a later entry).
+///
+/// ## Example:
+/// ```
+/// assert_eq!(parse_task("- [ ] call bank"), Some((' ', String::from("call bank"))));
+/// assert_eq!(parse_task("- [X] call bank"), Some(('x', String::from("call bank"))));
+/// assert_eq!(parse_task("- [food] | | |"), None);
+/// ```
+pub fn parse_task(line: &str) -> Option<(char, String)> {
+    let rest = line.trim_start().strip_prefix("- [")?;
+    let mut chars = rest.chars();
+    let state = chars.next()?.to_ascii_lowercase();
+    let text = chars.as_str().strip_prefix(']')?;
+    if ![' ', 'x', '>'].contains(&state) {
+        return None;
+    }
+    Some((state, text.trim().to_string()))
+}
+
+/// Checks if a tag is matched by the tag being searched for.
+///
+/// Tags can be nested using `/`, so that `[work/projx]` is a child of `work`.
+/// Searching for a tag matches the tag itself and all of its descendants.
+///
+/// ## Example:
+/// ```
+/// assert!(tag_matches("work/projx", "work"));
+/// assert!(tag_matches("work/projx", "work/projx"));
+/// assert!(!tag_matches("work/projy", "work/projx"));
+/// assert!(!tag_matches("workout", "work"));
+/// ```
+pub fn tag_matches(tag: &str, query: &str) -> bool {
+    tag == query || tag.starts_with(&format!("{}/", query))
+}
+
+/// Returns all records with the tag in the given file, within the span.
 /// Provides the date of the tag as well
 /// Returns (date, entry)
 /// By default, search for *tags*
 pub fn search_for_stuff(
     word: &str,
-    date: NaiveDate,
+    filename: &str,
+    span: &DateSpan,
     search: bool,
     approx: u32,
 ) -> (Vec<String>, Vec<String>) {
-    let filename: String = format!(
-        "{}/jrnl_folder/{}/{}_{}.md",
-        get_default_path(),
-        date.year(),
-        date.year(),
-        correct_month_nums(date.month())
-    );
-    let file_result: Result<File, std::io::Error> = File::open(&filename);
+    let file_result: Result<File, std::io::Error> = File::open(filename);
     let file: File = match file_result {
         Ok(file) => file,
         Err(e) => match e.kind() {
             // If the file is not found, say that it doesn't exist, instead of panicking.
-            ErrorKind::NotFound => {
-                eprintln!(
-                    "{}",
-                    format!("There are no entries in {}", &filename.underline()).red()
-                );
-                process::exit(1);
-            }
+            // Removed since it was listed; there is nothing in it to find
+            ErrorKind::NotFound => return (Vec::new(), Vec::new()),
             other => panic!("Error opening file: {other}"),
         },
     };
@@ -345,6 +684,8 @@ pub fn search_for_stuff(
 
     let mut reached_date_yet: bool = false;
     let mut entry_date_title = String::new();
+    // A file can have entries outside the span, like a yearly file
+    let mut in_span: bool = false;
 
     for line in reader.lines() {
         // Making another variable to manipulate it.
@@ -367,30 +708,30 @@ pub fn search_for_stuff(
             reached_date_yet = true;
             // Get the date of the following tags
             entry_date_title = cur_line.clone()[1..].trim().to_string();
+            in_span = NaiveDate::parse_from_str(&entry_date_title, "%Y-%m-%d")
+                .is_ok_and(|date| date >= span.start && date <= span.end);
+        }
+        if !in_span {
+            continue;
         }
 
-        if cur_line.contains(&format!("[{}]", word)) && !search {
-            let line_to_push = cur_line
-                .replace(&format!("[{}]", word), &format!("[{}]", word.cyan()))
-                .replace("- ", "")
-                .trim()
-                .to_string();
+        let mut matched_tags: Vec<String> = get_tags_from_line(&cur_line)
+            .into_iter()
+            .filter(|tag| tag_matches(tag, word))
+            .collect();
+        matched_tags.dedup();
+
+        if !matched_tags.is_empty() && !search {
+            let mut line_to_push = cur_line.clone();
+            for tag in matched_tags.iter() {
+                line_to_push =
+                    line_to_push.replace(&format!("[{}]", tag), &format!("[{}]", tag.cyan()));
+            }
+            let line_to_push = line_to_push.replace("- ", "").trim().to_string();
             tagged_entries.push(line_to_push);
             tagged_entry_dates.push(entry_date_title.clone());
         }
 
-        if cur_line.contains(&format!("[{}]", word)) && search {
-            println!("No matches for '{}' found in April, 2025", word.purple());
-            println!("{}:", "Help".green().bold());
-            println!(
-                "There exists a {} with a similar name: {}",
-                "tag".underline().red(),
-                word.bright_yellow().bold()
-            );
-            println!("Perhaps you meant to get the tag?");
-            process::exit(1);
-        }
-
         // Searching within words or across words
         if cur_line
             .clone()
@@ -440,7 +781,7 @@ pub fn search_for_stuff(
             .collect();
         let mut line_over = false;
         for thing in words {
-            if approx <= 0 {
+            if approx == 0 {
                 if (thing.to_lowercase() == word.to_lowercase()
                     || thing.to_lowercase() == word.to_lowercase() + "ed"
                     || thing.to_lowercase() == word.to_lowercase() + "d"
@@ -486,35 +827,79 @@ pub fn search_for_stuff(
     (tagged_entry_dates, tagged_entries)
 }
 
+/// Returns all records with the tag(or the word, if `search` is true) in the journal files
+/// of the span, as (dates, records). Exits if there are no entries in the span.
+pub fn search_in_span(
+    word: &str,
+    span: &DateSpan,
+    search: bool,
+    approx: u32,
+) -> (Vec<String>, Vec<String>) {
+    match try_search_in_span(word, span, search, approx) {
+        Some(found) => found,
+        None => {
+            eprintln!(
+                "{}",
+                format!(
+                    "There are no entries in {}",
+                    crate::report::span_title(span).underline()
+                )
+                .red()
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Like [`search_in_span`], but returns `None` instead of exiting if there are no entries
+/// in the span, for the callers which can not exit(like `jrnl tui`).
+pub fn try_search_in_span(
+    word: &str,
+    span: &DateSpan,
+    search: bool,
+    approx: u32,
+) -> Option<(Vec<String>, Vec<String>)> {
+    let files = get_journal_files_between(span.start, span.end);
+    if files.is_empty() {
+        return None;
+    }
+    let mut dates: Vec<String> = Vec::new();
+    let mut records: Vec<String> = Vec::new();
+    for filename in files {
+        let (file_dates, file_records) = search_for_stuff(word, &filename, span, search, approx);
+        dates.extend(file_dates);
+        records.extend(file_records);
+    }
+    Some((dates, records))
+}
+
+/// Checks if the word is used as a tag(or a parent of one, like `work` for `[work/projx]`)
+/// in the entries of the span.
+fn is_tag_in_span(word: &str, span: &DateSpan) -> bool {
+    get_journal_files_between(span.start, span.end)
+        .iter()
+        .any(|filename| {
+            let contents = fs::read_to_string(filename).unwrap_or_default();
+            let mut in_span = false;
+            contents.lines().any(|line| {
+                if let Some(heading) = line.strip_prefix("# ") {
+                    in_span = NaiveDate::parse_from_str(heading.trim(), "%Y-%m-%d")
+                        .is_ok_and(|date| date >= span.start && date <= span.end);
+                }
+                in_span
+                    && get_tags_from_line(line)
+                        .iter()
+                        .any(|tag| tag_matches(tag, word))
+            })
+        })
+}
+
 /// Returns NaiveDate when provided with a string
+///
+/// Understands everything that [`resolve_span`][crate::dates::resolve_span] does,
+/// and exits with suggestions if the date cannot be understood.
 pub fn parse_entry_args(args: &str) -> NaiveDate {
-    // Using Result<T> to handle errors nicely
-    let entry_date_result = NaiveDate::parse_from_str(args, "%Y-%m-%d");
-    let entry_date = match entry_date_result {
-        Ok(entry_date) => entry_date,
-        Err(e) => match e.kind() {
-            // ErrorKinds from chrono
-            ParseErrorKind::OutOfRange
-            | ParseErrorKind::Impossible
-            | ParseErrorKind::NotEnough
-            | ParseErrorKind::Invalid
-            | ParseErrorKind::TooShort
-            | ParseErrorKind::TooLong
-            | ParseErrorKind::BadFormat => {
-                eprintln!(
-                    "{}",
-                    "Please provide date in appropriate format: YYYY-MM-DD".red()
-                );
-                // Inquires date when wrong format of date
-                inquire_date()
-            }
-            e => {
-                eprintln!("{}", format!("An error has occured: {:?}", e).red());
-                process::exit(1);
-            }
-        },
-    };
-    entry_date
+    crate::dates::parse_day(args)
 }
 
 /// Handles the processing of tags and search
@@ -527,7 +912,7 @@ pub fn handle_tags(
     search: bool,
     approx: u32,
 ) {
-    let today: DateTime<Local> = Local::now(); //Get `now` time
+    let today = crate::dates::journal_today();
     let given_date_result = NaiveDate::from_ymd_opt(args_tag_year, args_tag_month, 1);
     let given_date = match given_date_result {
         Some(m) => m,
@@ -536,56 +921,29 @@ pub fn handle_tags(
                 "{}",
                 "Invalid year/month provided. Defaulting to today.".red()
             );
-            today.date_naive()
+            today
         }
     };
 
     let mut tags_date: Vec<String> = Vec::new();
     let mut tags_val: Vec<String> = Vec::new();
     let mut tags_food: Vec<Vec<String>> = Vec::new();
-    if year_provided && !month_provided {
-        // Loop over all possible files in the given year to find all tags in the year
-        let dir_name = format!("{}/jrnl_folder/{}/", get_default_path(), args_tag_year);
-        let paths_result = fs::read_dir(&dir_name);
-        let paths = match paths_result {
-            Ok(p) => p,
-            Err(e) => match e.kind() {
-                ErrorKind::NotFound => {
-                    eprintln!(
-                        "{}",
-                        format!("There are no entries in {}", &dir_name.underline()).red()
-                    );
-                    process::exit(1);
-                }
-                other => panic!("Error: {}", other),
-            },
-        };
-        for path in paths {
-            let name = path.unwrap().path().display().to_string();
-            let parts: Vec<&str> = name.split(&['/', '_', '.'][..]).collect();
-            let date = NaiveDate::from_ymd_opt(
-                parts[parts.len() - 3].parse().unwrap(),
-                parts[parts.len() - 2].parse().unwrap(),
-                1,
-            )
-            .unwrap_or(today.date_naive());
-            let tags_from_file = search_for_stuff(args_tag, date, search, approx);
-            tags_date.extend(tags_from_file.0);
-            tags_val.extend(tags_from_file.1);
-        }
+    let whole_year = year_provided && !month_provided;
+    let span = if whole_year {
+        DateSpan::year(args_tag_year)
     } else {
-        let tags_temp = search_for_stuff(args_tag, given_date, search, approx);
-        tags_date.extend(tags_temp.0);
-        if args_tag == "food" {
-            for item in tags_temp.1.clone().iter() {
-                let part_old = item.replace("[\u{1b}[36mfood\u{1b}[0m]", "");
-                let parts: Vec<String> =
-                    part_old.trim().split('|').map(|s| s.to_string()).collect();
-                tags_food.push(parts);
-            }
-        } else {
-            tags_val.extend(tags_temp.1);
+        DateSpan::month(given_date.year(), given_date.month()).unwrap()
+    };
+    let tags_temp = search_in_span(args_tag, &span, search, approx);
+    tags_date.extend(tags_temp.0);
+    if args_tag == "food" && !whole_year {
+        for item in tags_temp.1.clone().iter() {
+            let part_old = item.replace("[\u{1b}[36mfood\u{1b}[0m]", "");
+            let parts: Vec<String> = part_old.trim().split('|').map(|s| s.to_string()).collect();
+            tags_food.push(parts);
         }
+    } else {
+        tags_val.extend(tags_temp.1);
     }
 
     // Following part is to order the entries by date
@@ -611,7 +969,7 @@ pub fn handle_tags(
     let mut ydays_vec = Vec::new();
     let mut month_days: HashMap<u32, Vec<u32>> = HashMap::new();
     for date in tags_date.clone() {
-        if date != "" {
+        if !date.is_empty() {
             let day: u32 = date.split('-').collect::<Vec<&str>>()[2]
                 .parse::<u32>()
                 .unwrap_or(0);
@@ -630,7 +988,7 @@ pub fn handle_tags(
         None => (100, 30),
     };
 
-    if tags.0.len() == 0 {
+    if tags.0.is_empty() {
         // Other 3 cases included in the else clause.
         if year_provided && !month_provided {
             if search {
@@ -681,9 +1039,9 @@ pub fn handle_tags(
             );
             process::exit(1);
         }
-        if read_config().0.when_pager == String::from("always") {
+        if read_config().0.when_pager == "always" {
             make_pager(&format!("{}", make_food_table((tags_date, tags_food))));
-        } else if read_config().0.when_pager == String::from("default") {
+        } else if read_config().0.when_pager == "default" {
             if tags_food.len() >= 5 {
                 make_pager(&format!("{}", make_food_table((tags_date, tags_food))));
             } else {
@@ -693,9 +1051,9 @@ pub fn handle_tags(
             println!("{}", make_food_table((tags_date, tags_food)));
         }
     } else {
-        if read_config().0.when_pager == String::from("always") {
+        if read_config().0.when_pager == "always" {
             make_pager(&format!("{}", make_tags_table(tags)));
-        } else if read_config().0.when_pager == String::from("default") {
+        } else if read_config().0.when_pager == "default" {
             if tags.0.len() >= 5 {
                 make_pager(&format!("{}", make_tags_table(tags)));
             } else {
@@ -705,11 +1063,21 @@ pub fn handle_tags(
             println!("{}", make_tags_table(tags));
         }
     }
+    if search && is_tag_in_span(args_tag, &span) {
+        println!(
+            "{}: There is also a {} {} in {}. Use `-t {}` to list only its records.",
+            "HELP".green().bold(),
+            "tag".underline(),
+            args_tag.cyan(),
+            crate::report::span_title(&span),
+            args_tag
+        );
+    }
 
     if year_provided && !month_provided {
         let mut calendar = Vec::new();
         for (month, day) in ydays_vec {
-            month_days.entry(month).or_insert_with(Vec::new).push(day);
+            month_days.entry(month).or_default().push(day);
         }
         for (month, days) in month_days {
             calendar.push((month, print_calendar(args_tag_year, month, days)));
@@ -744,16 +1112,9 @@ pub fn handle_tags(
 }
 
 /// Given an entry_date, if it exists, opens the editor at that position.
-/// The editor is decided based upon the configuration
+/// The editor is decided based upon the configuration, and the file upon the `layout`
 pub fn open_editor(entry_date: String) {
-    let parts: Vec<&str> = entry_date.split('-').collect();
-    let filename = format!(
-        "{}/jrnl_folder/{}/{}_{}.md",
-        get_default_path(),
-        parts[0],
-        parts[0],
-        parts[1]
-    );
+    let filename = journal_file_for_writing(parse_entry_args(&entry_date));
     let made_new_file = !check_file_existed(&filename);
 
     if made_new_file {
@@ -779,341 +1140,186 @@ pub fn open_editor(entry_date: String) {
                         cmd_arg = format!("{}:{}", filename, no);
                     }
                 } else {
-                    cmd_arg = format!("{}", filename);
+                    cmd_arg = filename.to_string();
                 }
+                start_editing(&filename);
                 process::Command::new(read_config().0.editor)
                     .arg(cmd_arg)
                     .status()
                     .expect("Failed to execute process");
+                end_editing(&filename);
                 return;
             }
         }
     }
 }
 
-/// Generates a report for a month.
-///
-/// ## Sample output:
-/// Note that colors are present, but cannot be shown here.
-///
-/// ```text
-/// Report for April, 2025
+/// Returns the range of lines(0-indexed, end exclusive) that make up the entry
+/// for `entry_date`, as located by [`get_headings`].
 ///
-/// Number of entries this month: 12
-///
-///      April 2025
-/// Mo Tu We Th Fr Sa Su
-///     1  2  3  4  5  6
-///  7  8  9 10 11 12 13
-/// 14 15 16 17 18 19 20
-/// 21 22 23 24 25 26 27
-/// 28 29 30
-///
-/// Most used tags:
-/// ╭───────────┬───────────╮
-/// │ Tag       ┆ Frequency │
-/// ╞═══════════╪═══════════╡
-/// │ tag1      ┆ 12        │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag2      ┆ 6         │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag3      ┆ 2         │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag4      ┆ 2         │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag5      ┆ 2         │
-/// ╰───────────┴───────────╯
-/// ```
-pub fn gen_report(year: i32, month: u32) {
-    let filename = format!(
-        "{}/jrnl_folder/{}/{}_{}.md",
-        get_default_path(),
-        year,
-        year,
-        correct_month_nums(month)
-    );
-    let (headings, no_of_entries) = get_headings(&filename);
-    println!(
-        "{}",
-        format!("Report for {}, {}\n", month_no_to_name(month), year)
-            .bold()
-            .cyan()
-            .underline()
-    );
-
-    println!(
-        "{}",
-        format!(
-            "Number of entries this month: {}\n",
-            no_of_entries.len().to_string().bold()
-        )
-        .yellow()
-    );
-
-    let mut dates: Vec<u32> = Vec::new();
-    for heading in headings {
-        let parts: Vec<&str> = heading.split('-').collect();
-        dates.push(parts[2].to_string().parse::<u32>().unwrap_or(0));
+/// The range includes the `### WEEKDAY` line(s) right above the heading, and stops
+/// right before the `###` line(s) of the next entry, if any. Blank lines at the end
+/// of the entry are left out.
+pub fn get_entry_span(filename: &str, lines: &[&str], entry_date: &str) -> Option<(usize, usize)> {
+    let (headings, corr_line_no) = get_headings(filename);
+    let pos = headings
+        .iter()
+        .position(|head| head[1..].trim() == entry_date)?;
+
+    let mut start = corr_line_no[pos] as usize - 1;
+    while start > 0 && lines[start - 1].starts_with("### ") {
+        start -= 1;
     }
-    println!("{}", print_calendar(year, month, dates));
-
-    // Most used tags
-    println!("{}", "Most used tags:".yellow().bold());
-    let file_tags = get_tags_from_file(&filename);
-    let mut freq_map: HashMap<String, u32> = HashMap::new();
-    for item in file_tags.iter() {
-        let count = freq_map.entry(item.to_string()).or_insert(0);
-        *count += 1;
+    let mut end = match corr_line_no.get(pos + 1) {
+        Some(no) => *no as usize - 1,
+        None => lines.len(),
+    };
+    while end > start + 1
+        && (lines[end - 1].starts_with("### ") || lines[end - 1].trim().is_empty())
+    {
+        end -= 1;
     }
+    Some((start, end))
+}
 
-    let mut sorted: Vec<_> = freq_map.iter().collect();
-    sorted.sort_by_key(|a| a.1);
-
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec!["Tag".green(), "Frequency".green()]);
-    let mut no_of_rows = 0;
-    for (key, value) in sorted.iter().rev() {
-        table.add_row(vec![key.to_owned().to_owned(), value.to_string()]);
-        no_of_rows += 1;
-        if no_of_rows >= read_config().0.max_rows {
-            break;
-        }
+/// Adds a record(like `- 14:02-15:10 [work] code review`) at the end of the entry of the date.
+///
+/// The month file and the heading are made through [`add_info_to_file`] if needed,
+/// as when opening the entry in the editor.
+pub fn add_record_to_entry(date: NaiveDate, record: &str) {
+    let entry_date = date.format("%Y-%m-%d").to_string();
+    let filename = journal_file_for_writing(date);
+    let _lock = lock_journal();
+    if !check_file_existed(&filename) {
+        println!("Made a new file: {}", filename.underline());
     }
-    println!("{}", table);
-
-    let events_vec = read_events();
-    let today = NaiveDate::from_ymd_opt(
-        Local::now().year(),
-        Local::now().month(),
-        Local::now().day(),
-    )
-    .unwrap();
-    let mut upcoming: Vec<String> = Vec::new();
-    let mut completed: Vec<String> = Vec::new();
-    for event in events_vec {
-        let diff = (event.0 - today).num_days();
-        if diff == 0 {
-            upcoming.push(format!(
-                "[{}] {}: {}",
-                event.0.format("%Y-%m-%d").to_string().cyan(),
-                "TODAY!".red().underline(),
-                event.1
-            ));
-        } else if diff == 1 {
-            upcoming.push(format!(
-                "[{}] {} day from now: {}",
-                event.0.format("%Y-%m-%d").to_string().cyan(),
-                diff.to_string().yellow(),
-                event.1
-            ));
-        } else if diff > 1 && diff <= 7 {
-            upcoming.push(format!(
-                "[{}] {} days from now: {}",
-                event.0.format("%Y-%m-%d").to_string().cyan(),
-                diff.to_string().yellow(),
-                event.1
-            ));
-        } else if diff > 8 && diff <= 30 {
-            upcoming.push(format!(
-                "[{}] {} days from now: {}",
-                event.0.format("%Y-%m-%d").to_string().cyan(),
-                diff.to_string().bold(),
-                event.1
-            ));
-        } else if diff == -1 {
-            completed.push(format!(
-                "[{}] {} day ago: {}",
-                event.0.format("%Y-%m-%d").to_string().cyan(),
-                (-diff).to_string().bold(),
-                event.1
-            ));
-        } else if diff < -1 && diff >= -30 {
-            completed.push(format!(
-                "[{}] {} days ago: {}",
-                event.0.format("%Y-%m-%d").to_string().cyan(),
-                (-diff).to_string().bold(),
-                event.1
-            ));
-        }
+    match add_info_to_file(&filename, entry_date.clone()) {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
     }
 
-    println!("\n{}", "Upcoming Events:".yellow().bold());
-    for item in upcoming {
-        println!("{}", item);
-    }
-    println!("\n{}", "Recently completed Events:".yellow().bold());
-    for item in completed {
-        println!("{}", item);
+    let contents = match fs::read_to_string(&filename) {
+        Ok(data) => data,
+        Err(e) => panic!("Error reading file: {}", e),
+    };
+    let lines: Vec<&str> = contents.split('\n').collect();
+    let (_, end) = match get_entry_span(&filename, &lines, &entry_date) {
+        Some(span) => span,
+        None => panic!(
+            "An error occured: the entry for {} was not added",
+            entry_date
+        ),
+    };
+    let mut new_lines: Vec<&str> = lines.clone();
+    new_lines.insert(end, record);
+    match write_journal(&filename, &new_lines.join("\n")) {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
     }
 }
 
-/// Generates a report for a year.
-///
-/// ## Sample output
-/// Note that colors will be shown.
-/// In the calendar, the dates when entries are present will be highlighted
-///
-/// ```text
-/// Report for 2025
+/// Opens only the given date's entry in the editor, using a temporary file.
 ///
-/// Number of entries this year: 17
-/// March: 5
-/// April: 12
-/// May: 0
-///
-///       March 2025             April 2025             May 2025
-///  Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su
-///                  1  2       1  2  3  4  5  6             1  2  3  4
-///   3  4  5  6  7  8  9    7  8  9 10 11 12 13    5  6  7  8  9 10 11
-///  10 11 12 13 14 15 16   14 15 16 17 18 19 20   12 13 14 15 16 17 18
-///  17 18 19 20 21 22 23   21 22 23 24 25 26 27   19 20 21 22 23 24 25
-///  24 25 26 27 28 29 30   28 29 30               26 27 28 29 30 31
-///  31
-///
-///
-/// Most used tags:
-/// ╭───────────┬───────────╮
-/// │ Tag       ┆ Frequency │
-/// ╞═══════════╪═══════════╡
-/// │ food      ┆ 17        │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag2      ┆ 10        │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag3      ┆ 4         │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag4      ┆ 3         │
-/// ├╌╌╌╌╌╌╌╌╌╌╌┼╌╌╌╌╌╌╌╌╌╌╌┤
-/// │ tag5      ┆ 2         │
-/// ╰───────────┴───────────╯
-/// ```
-pub fn gen_report_year(year: i32) {
-    // Loop over all possible files in the given year to find all tags in the year
-    let dir_name = format!("{}/jrnl_folder/{}/", get_default_path(), year);
-    let paths_result = fs::read_dir(&dir_name);
-    let paths = match paths_result {
-        Ok(p) => p,
-        Err(e) => match e.kind() {
-            ErrorKind::NotFound => {
-                eprintln!(
-                    "{}",
-                    format!("There are no entries in {}", &dir_name.underline()).red()
-                );
-                process::exit(1);
-            }
-            other => panic!("Error: {}", other),
-        },
-    };
-
-    let mut total_entries = 0;
-    let mut final_tags: Vec<(String, u32)> = Vec::new();
-    let mut monthly_entries: HashMap<u32, u32> = HashMap::new();
-    let mut monthly_entries_vec: Vec<(u32, u32)> = Vec::new();
-    let mut calendar: Vec<(u32, String)> = Vec::new();
-    let mut freq_map: HashMap<String, u32> = HashMap::new();
-    for path in paths {
-        let name = path.unwrap().path().display().to_string();
-        let parts: Vec<&str> = name.split(&['/', '_', '.'][..]).collect();
-        let (year, month): (i32, u32) = (
-            parts[parts.len() - 3].parse().unwrap(),
-            parts[parts.len() - 2].parse().unwrap(),
-        );
-
-        // Stuff
-        let filename = format!(
-            "{}/jrnl_folder/{}/{}_{}.md",
-            get_default_path(),
-            year,
-            year,
-            correct_month_nums(month)
-        );
-        let (headings, curr_no_of_entries) = get_headings(&filename);
-        total_entries += curr_no_of_entries.len();
-
-        monthly_entries.insert(month, curr_no_of_entries.len().try_into().unwrap());
-        let mut dates: Vec<u32> = Vec::new();
-        for heading in headings {
-            let parts: Vec<&str> = heading.split('-').collect();
-            dates.push(parts[2].to_string().parse::<u32>().unwrap_or(0));
-        }
-        calendar.push((month, print_calendar(year, month, dates)));
-
-        let file_tags = get_tags_from_file(&filename);
-        for item in file_tags.iter() {
-            let count = freq_map.entry(item.to_string()).or_insert(0);
-            *count += 1;
-        }
+/// After the editor exits, the edited entry is checked(it must still contain only
+/// the `# YYYY-MM-DD` heading of this date), and then spliced back into the journal file.
+/// If the journal file was changed by something else in the meantime, nothing is written,
+/// and the edited entry is left in the temporary file.
+pub fn open_editor_isolated(entry_date: String) {
+    let filename = journal_file_for_writing(parse_entry_args(&entry_date));
+    let made_new_file = !check_file_existed(&filename);
 
-        monthly_entries_vec.extend(vec![(month, curr_no_of_entries.len().try_into().unwrap())]);
-    }
-    for (key, value) in freq_map {
-        final_tags.extend(vec![(key, value)]);
+    if made_new_file {
+        println!("Made a new file: {}", filename.underline());
     }
-    final_tags.sort_by_key(|a| a.1);
-    monthly_entries_vec.sort_by_key(|a| a.0);
-    calendar.sort_by_key(|a| a.0);
-
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec!["Tag".green(), "Frequency".green()]);
-    let mut no_of_rows = 0;
-    for (key, value) in final_tags.iter().rev() {
-        table.add_row(vec![key.to_owned().to_owned(), value.to_string()]);
-        no_of_rows += 1;
-        if no_of_rows >= read_config().0.max_rows {
-            break;
-        }
+
+    let added_date_result = add_info_to_file(&filename, entry_date.clone());
+    match added_date_result {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
     }
 
-    let mut cal = Table::new();
-    // Width to get the number of columns to push to the table when
-    // making the calendar grid
-    let (w, _h) = match term_size::dimensions() {
-        Some((w, h)) => (w, h),
-        None => (100, 30),
+    let original = match fs::read_to_string(&filename) {
+        Ok(data) => data,
+        Err(e) => panic!("Error reading file: {}", e),
+    };
+    let lines: Vec<&str> = original.split('\n').collect();
+    let (start, end) = match get_entry_span(&filename, &lines, &entry_date) {
+        Some(span) => span,
+        None => {
+            eprintln!(
+                "{}",
+                format!("Entry does not exist for {}", entry_date).red()
+            );
+            process::exit(1);
+        }
     };
-    let w = w / 23; // Each calendar takes 23 chars
-    cal.set_content_arrangement(ContentArrangement::Dynamic)
-        .load_preset(NOTHING);
 
-    let mut cal_str: Vec<String> = Vec::new();
-    for (_, item) in calendar {
-        cal_str.push(item);
-    }
-    let chunks: Vec<&[String]> = cal_str.chunks(w).collect();
-    for item in chunks {
-        cal.add_row(item);
+    // A new file with a unique name, readable only by the user, so that two sessions never
+    // share it. It is kept(not removed when dropped), to hold the changes if they can not be
+    // written back.
+    let tmp_file = tempfile::Builder::new()
+        .prefix(&format!("jrnl_{}_", entry_date))
+        .suffix(".md")
+        .tempfile();
+    let (mut tmp_file, tmp_path) = match tmp_file.and_then(|file| Ok(file.keep()?)) {
+        Ok(kept) => kept,
+        Err(e) => panic!("An error occured: {}", e),
+    };
+    let tmp_name = tmp_path.display().to_string();
+    let write_result = tmp_file.write_all((lines[start..end].join("\n") + "\n").as_bytes());
+    match write_result {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
     }
+    drop(tmp_file);
 
-    println!(
-        "{}",
-        format!("Report for {}\n", year).bold().cyan().underline()
-    );
+    process::Command::new(read_config().0.editor)
+        .arg(&tmp_name)
+        .status()
+        .expect("Failed to execute process");
 
-    println!(
-        "{}",
-        format!(
-            "Number of entries this year: {}",
-            total_entries.to_string().bold()
-        )
-        .yellow()
-        .underline()
-    );
+    let edited = match fs::read_to_string(&tmp_path) {
+        Ok(data) => data,
+        Err(e) => panic!("Error reading file: {}", e),
+    };
+    let edited = edited.trim_end_matches('\n');
+
+    // The entry must keep its own heading, and must not contain any other entry.
+    let edited_headings: Vec<&str> = edited.lines().filter(|l| l.starts_with("# ")).collect();
+    if edited_headings != vec![format!("# {}", entry_date)] {
+        eprintln!(
+            "{}: The edited entry must contain exactly one heading: `# {}`",
+            "ERROR".red().bold(),
+            entry_date
+        );
+        eprintln!("Your changes are kept in {}", tmp_name.underline());
+        process::exit(1);
+    }
 
-    for (k, v) in monthly_entries_vec {
-        println!("{}: {}", month_no_to_name(k), v);
+    let _lock = lock_journal();
+    let current = fs::read_to_string(&filename).unwrap_or_default();
+    if current != original {
+        eprintln!(
+            "{}: {} was changed while you were editing.",
+            "CONFLICT".yellow().bold(),
+            filename.underline()
+        );
+        eprintln!(
+            "Nothing was written. Your changes are kept in {}",
+            tmp_name.underline()
+        );
+        process::exit(1);
     }
-    println!("\n{}", cal);
 
-    println!();
-    println!("{}", "Most used tags:".yellow().underline());
-    println!("{}", table);
+    let mut new_lines: Vec<&str> = Vec::new();
+    new_lines.extend(&lines[..start]);
+    new_lines.extend(edited.split('\n'));
+    new_lines.extend(&lines[end..]);
+    match write_journal(&filename, &new_lines.join("\n")) {
+        Ok(_) => (),
+        Err(e) => panic!("An error occured: {}", e),
+    }
+    let _ = fs::remove_file(&tmp_path);
 }
 
 /// Read the `events.md` file located in `jrnl_folder`, and returns a Vector containing
@@ -1160,8 +1366,11 @@ pub fn read_events() -> Vec<(NaiveDate, String)> {
                 let new_item: u32 = item.parse().unwrap_or(0);
                 u32_date_parts.push(new_item);
             }
-            let date_result =
-                NaiveDate::from_ymd_opt(Local::now().year(), u32_date_parts[0], u32_date_parts[1]);
+            let date_result = NaiveDate::from_ymd_opt(
+                crate::dates::journal_today().year(),
+                u32_date_parts[0],
+                u32_date_parts[1],
+            );
             let date = match date_result {
                 Some(d) => d,
                 None => {
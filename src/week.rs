@@ -0,0 +1,54 @@
+//! Contains the week-oriented view, `jrnl week`. The weekly report(`--gen-report --week`)
+//! is made by [`gen_report`][crate::report::gen_report].
+//!
+//! A week can span two month files(or two years), so this goes over the dates of the week,
+//! instead of a single month file.
+use crate::dates::DateSpan;
+use crate::funcs::*;
+use crate::utils::*;
+use chrono::{Datelike, Duration, NaiveDate};
+use colored::Colorize;
+
+/// Returns the title of the week, like "Week 42 of 2026 (2026-10-12 to 2026-10-18)".
+///
+/// The ISO week number is the one of the middle of the week, so that it is the same
+/// whether the week starts on Monday or Sunday.
+pub fn week_title(span: &DateSpan) -> String {
+    let iso = (span.start + Duration::days(3)).iso_week();
+    format!(
+        "Week {} of {} ({} to {})",
+        iso.week(),
+        iso.year(),
+        span.start.format("%Y-%m-%d"),
+        span.end.format("%Y-%m-%d")
+    )
+}
+
+/// Returns the dates(YYYY-MM-DD) in the week which have an entry, sorted.
+fn entry_dates(span: &DateSpan) -> Vec<String> {
+    let start = span.start.format("%Y-%m-%d").to_string();
+    let end = span.end.format("%Y-%m-%d").to_string();
+    let mut dates: Vec<String> = get_all_entries()
+        .into_iter()
+        .map(|(date, _)| date)
+        .filter(|date| *date >= start && *date <= end)
+        .collect();
+    // The same date can have more than one heading, but `get_entry` prints all of them
+    dates.dedup();
+    dates
+}
+
+/// Prints every entry of the week.
+pub fn print_week(span: &DateSpan) {
+    let mut output = format!("{}\n\n", week_title(span).bold().cyan().underline());
+    let dates = entry_dates(span);
+    if dates.is_empty() {
+        output.push_str("No entries this week.\n");
+    }
+    for date in dates.iter() {
+        if let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            output.push_str(&format!("{}\n", get_entry(date)));
+        }
+    }
+    print_with_pager(&output, output.lines().count());
+}